@@ -0,0 +1,245 @@
+//! Derive macros for [imgui-rs](https://docs.rs/imgui).
+//!
+//! Enable the `derive` feature of `imgui` and use the macros re-exported from there, rather
+//! than depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Error, Expr, Fields, LitStr,
+    Member, Result,
+};
+
+/// Derives `imgui::Inspect`, drawing an editor for every field of a struct, or a combo box
+/// choosing a variant of an enum with only unit variants.
+///
+/// Fields are labelled with their name and edited with their own `Inspect` implementation. The
+/// `#[inspect(...)]` attribute customizes a field:
+///
+/// - `skip`: doesn't show the field.
+/// - `label = "..."`: uses another label.
+/// - `min = ..., max = ...`: edits a number with a slider in that range.
+/// - `speed = ...`: edits a number with a drag of that speed, clamped to `min`/`max` if given.
+/// - `format = "..."`: display format of numbers, as a printf string.
+/// - `color`: edits a `[f32; 3]`, `[f32; 4]` or mint vector with a color editor.
+/// - `read_only`: shows the field disabled.
+/// - `tooltip = "..."`: shows a tooltip when the field is hovered.
+/// - `nested`: shows the field inside a tree node.
+///
+/// Enum variants accept `label = "..."`.
+///
+/// Type parameters of the struct must implement `Inspect` too.
+#[proc_macro_derive(Inspect, attributes(inspect))]
+pub fn derive_inspect(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_inspect(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_inspect(input: DeriveInput) -> Result<TokenStream2> {
+    let body = match &input.data {
+        Data::Struct(data) => inspect_struct(&data.fields)?,
+        Data::Enum(data) => {
+            let mut labels = Vec::new();
+            let mut variants = Vec::new();
+            for variant in &data.variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(Error::new(
+                        variant.span(),
+                        "`Inspect` can only be derived for enums with unit variants",
+                    ));
+                }
+                let attrs = FieldAttrs::parse(&variant.attrs)?;
+                labels.push(attrs.label.unwrap_or_else(|| variant.ident.to_string()));
+                variants.push(&variant.ident);
+            }
+            let indices = 0..variants.len();
+            let indices2 = indices.clone();
+            quote! {
+                let mut index: usize = match self {
+                    #(Self::#variants => #indices,)*
+                };
+                let changed = ui.combo_simple_string(label, &mut index, &[#(#labels),*]);
+                if changed {
+                    *self = match index {
+                        #(#indices2 => Self::#variants,)*
+                        _ => unreachable!(),
+                    };
+                }
+                changed
+            }
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span(),
+                "`Inspect` cannot be derived for unions",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    let params: Vec<_> = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let where_clause = generics.make_where_clause();
+    for param in params {
+        where_clause
+            .predicates
+            .push(parse_quote!(#param: ::imgui::Inspect));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::imgui::Inspect for #name #ty_generics #where_clause {
+            fn inspect_with(
+                &mut self,
+                ui: &::imgui::Ui,
+                label: &str,
+                options: &::imgui::InspectOptions<'_>,
+            ) -> bool {
+                let _ = options;
+                #body
+            }
+        }
+    })
+}
+
+fn inspect_struct(fields: &Fields) -> Result<TokenStream2> {
+    let mut edits = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let attrs = FieldAttrs::parse(&field.attrs)?;
+        if attrs.skip {
+            continue;
+        }
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        let label = attrs.label.clone().unwrap_or_else(|| match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => index.to_string(),
+        });
+        edits.push(attrs.edit(&member, &label)?);
+    }
+    Ok(quote! {
+        let _id = ui.push_id(label);
+        let mut changed = false;
+        #(#edits)*
+        changed
+    })
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    skip: bool,
+    label: Option<String>,
+    min: Option<Expr>,
+    max: Option<Expr>,
+    speed: Option<Expr>,
+    format: Option<LitStr>,
+    color: bool,
+    read_only: bool,
+    tooltip: Option<LitStr>,
+    nested: bool,
+}
+
+impl FieldAttrs {
+    fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = FieldAttrs::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("inspect")) {
+            attr.parse_nested_meta(|meta| {
+                let path = &meta.path;
+                if path.is_ident("skip") {
+                    parsed.skip = true;
+                } else if path.is_ident("label") {
+                    parsed.label = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if path.is_ident("min") {
+                    parsed.min = Some(meta.value()?.parse()?);
+                } else if path.is_ident("max") {
+                    parsed.max = Some(meta.value()?.parse()?);
+                } else if path.is_ident("speed") {
+                    parsed.speed = Some(meta.value()?.parse()?);
+                } else if path.is_ident("format") {
+                    parsed.format = Some(meta.value()?.parse()?);
+                } else if path.is_ident("color") {
+                    parsed.color = true;
+                } else if path.is_ident("read_only") {
+                    parsed.read_only = true;
+                } else if path.is_ident("tooltip") {
+                    parsed.tooltip = Some(meta.value()?.parse()?);
+                } else if path.is_ident("nested") {
+                    parsed.nested = true;
+                } else {
+                    return Err(meta.error("unknown `inspect` attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(parsed)
+    }
+
+    fn edit(&self, member: &Member, label: &str) -> Result<TokenStream2> {
+        let range = match (&self.min, &self.max) {
+            (Some(min), Some(max)) => quote!(Some(((#min) as f64, (#max) as f64))),
+            (None, None) => quote!(None),
+            (Some(bound), None) | (None, Some(bound)) => {
+                return Err(Error::new(
+                    bound.span(),
+                    "`min` and `max` must be given together",
+                ))
+            }
+        };
+        let speed = match &self.speed {
+            Some(speed) => quote!(Some((#speed) as f32)),
+            None => quote!(None),
+        };
+        let format = match &self.format {
+            Some(format) => quote!(Some(#format)),
+            None => quote!(None),
+        };
+        let color = self.color;
+        let disabled = self
+            .read_only
+            .then(|| quote!(let _disabled = ui.begin_disabled(true);));
+        let tooltip = self.tooltip.as_ref().map(|tooltip| {
+            quote! {
+                if ui.is_item_hovered() {
+                    ui.tooltip_text(#tooltip);
+                }
+            }
+        });
+        let inspect = quote! {
+            changed |= ::imgui::Inspect::inspect_with(&mut self.#member, ui, #label, &options);
+        };
+        let inspect = if self.nested {
+            quote! {
+                let node = ui.tree_node(#label);
+                #tooltip
+                if let Some(_node) = node {
+                    #inspect
+                }
+            }
+        } else {
+            quote! {
+                #inspect
+                #tooltip
+            }
+        };
+        Ok(quote! {
+            {
+                #disabled
+                let options = ::imgui::InspectOptions {
+                    range: #range,
+                    speed: #speed,
+                    format: #format,
+                    color: #color,
+                };
+                #inspect
+            }
+        })
+    }
+}
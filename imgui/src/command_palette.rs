@@ -0,0 +1,325 @@
+use std::cmp::Reverse;
+
+use crate::text_filter::{MatchMode, SearchFilter};
+use crate::{
+    HistoryDirection, InputTextCallback, InputTextCallbackHandler, Key, ListClipper,
+    TextCallbackData, Ui,
+};
+
+/// Number of commands visible in the palette without scrolling.
+const VISIBLE_COMMANDS: usize = 10;
+
+/// A command registered in a [`CommandPalette`].
+#[derive(Clone, Debug)]
+pub struct Command<T> {
+    name: String,
+    shortcut: Option<String>,
+    value: T,
+}
+
+impl<T> Command<T> {
+    /// Creates a command with the name shown in the palette, and the value returned when it is
+    /// chosen.
+    pub fn new(name: impl Into<String>, value: T) -> Self {
+        Command {
+            name: name.into(),
+            shortcut: None,
+            value,
+        }
+    }
+
+    /// Sets the shortcut label shown next to the command, such as `"Ctrl+S"`.
+    ///
+    /// This is only a label; the shortcut itself must be handled by the application.
+    #[inline]
+    pub fn shortcut(mut self, shortcut: impl Into<String>) -> Self {
+        self.shortcut = Some(shortcut.into());
+        self
+    }
+
+    /// Returns the name of the command.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the value of the command.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+}
+
+/// A modal popup listing commands which are fuzzy-ranked as the user types.
+///
+/// The popup opens with Ctrl+Shift+P (see [`CommandPalette::open_with_shortcut`]) or
+/// [`CommandPalette::open`]. Up and Down move the selection, Tab completes the query to the
+/// selected command, Enter or a click chooses it and Escape closes the palette.
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// #[derive(Clone, Copy)]
+/// enum Action {
+///     Save,
+///     Quit,
+/// }
+///
+/// let mut palette = CommandPalette::new("Commands");
+/// palette.add(Command::new("File: Save", Action::Save).shortcut("Ctrl+S"));
+/// palette.add(Command::new("Quit", Action::Quit));
+///
+/// match palette.build(ui) {
+///     Some(Action::Save) => println!("save"),
+///     Some(Action::Quit) => println!("quit"),
+///     None => {}
+/// }
+/// ```
+#[derive(Debug)]
+pub struct CommandPalette<T> {
+    id: String,
+    commands: Vec<Command<T>>,
+    filter: SearchFilter,
+    /// Indices into `commands`, best match first.
+    ranked: Vec<usize>,
+    selected: usize,
+    open_requested: bool,
+    shortcut: bool,
+}
+
+impl<T> CommandPalette<T> {
+    /// Creates an empty palette. `id` is used as the id of its popup.
+    pub fn new(id: impl Into<String>) -> Self {
+        CommandPalette {
+            id: id.into(),
+            commands: Vec::new(),
+            filter: SearchFilter::new("##query").mode(MatchMode::Fuzzy),
+            ranked: Vec::new(),
+            selected: 0,
+            open_requested: false,
+            shortcut: true,
+        }
+    }
+
+    /// Enables/disables opening the palette with Ctrl+Shift+P.
+    ///
+    /// Enabled by default.
+    #[inline]
+    pub fn open_with_shortcut(mut self, value: bool) -> Self {
+        self.shortcut = value;
+        self
+    }
+
+    /// Registers a command.
+    pub fn add(&mut self, command: Command<T>) {
+        self.commands.push(command);
+        self.rank();
+    }
+
+    /// Returns the registered commands.
+    pub fn commands(&self) -> &[Command<T>] {
+        &self.commands
+    }
+
+    /// Removes all commands.
+    pub fn clear(&mut self) {
+        self.commands.clear();
+        self.rank();
+    }
+
+    /// Opens the palette on the next build, with an empty query.
+    pub fn open(&mut self) {
+        self.open_requested = true;
+    }
+
+    /// Returns the commands matching the current query, best match first.
+    pub fn matches(&self) -> impl Iterator<Item = &Command<T>> + '_ {
+        self.ranked.iter().map(|&index| &self.commands[index])
+    }
+
+    fn rank(&mut self) {
+        let commands = &self.commands;
+        let mut ranked: Vec<(usize, i32)> = commands
+            .iter()
+            .enumerate()
+            .filter_map(|(index, command)| {
+                let found = self.filter.find(&command.name)?;
+                Some((index, found.score))
+            })
+            .collect();
+        // Stable, so commands with equal scores stay in registration order.
+        ranked.sort_by_key(|&(_, score)| Reverse(score));
+        self.ranked = ranked.into_iter().map(|(index, _)| index).collect();
+        self.selected = 0;
+    }
+
+    /// Builds the palette, returning the value of the command chosen this frame.
+    pub fn build(&mut self, ui: &Ui) -> Option<&T> {
+        let io = ui.io();
+        let shortcut_pressed = io.key_ctrl && io.key_shift && ui.is_key_pressed(Key::P);
+        if self.open_requested || self.shortcut && shortcut_pressed {
+            self.open_requested = false;
+            self.filter.clear();
+            self.rank();
+            ui.open_popup(&self.id);
+        }
+
+        let mut chosen = None;
+        // The id is copied, as the palette is borrowed mutably while building.
+        let id = self.id.clone();
+        ui.modal_popup_config(&id)
+            .title_bar(false)
+            .resizable(false)
+            .always_auto_resize(true)
+            .build(|| {
+                if ui.is_window_appearing() {
+                    ui.set_keyboard_focus_here();
+                }
+                let mut query = self.filter.text().to_owned();
+                let mut scroll_to_selected = false;
+                let completion = self
+                    .ranked
+                    .get(self.selected)
+                    .map(|&index| self.commands[index].name.as_str());
+                let navigation = Navigation {
+                    selected: &mut self.selected,
+                    count: self.ranked.len(),
+                    completion,
+                    moved: &mut scroll_to_selected,
+                };
+                let width = ui.calc_text_size("M")[0] * 40.0;
+                ui.set_next_item_width(width);
+                let entered = ui
+                    .input_text("##query", &mut query)
+                    .hint("Type a command")
+                    .enter_returns_true(true)
+                    .callback(
+                        InputTextCallback::HISTORY | InputTextCallback::COMPLETION,
+                        navigation,
+                    )
+                    .build();
+                if query != self.filter.text() {
+                    self.filter.set_filter(query);
+                    self.rank();
+                }
+
+                let row_height = ui.text_line_height_with_spacing();
+                let rows = self.ranked.len().clamp(1, VISIBLE_COMMANDS);
+                let padding = ui.clone_style().window_padding[1];
+                let height = rows as f32 * row_height + padding * 2.0;
+                ui.child_window("##commands")
+                    .size([width, height])
+                    .build(|| {
+                        if scroll_to_selected {
+                            let top = self.selected as f32 * row_height;
+                            let visible = height - padding * 2.0;
+                            if top < ui.scroll_y() {
+                                ui.set_scroll_y(top);
+                            } else if top + row_height > ui.scroll_y() + visible {
+                                ui.set_scroll_y(top + row_height - visible);
+                            }
+                        }
+                        let clipper = ListClipper::new(self.ranked.len() as i32)
+                            .items_height(row_height)
+                            .begin(ui);
+                        for row in clipper.iter() {
+                            let row = row as usize;
+                            let command = &self.commands[self.ranked[row]];
+                            let _id = ui.push_id_usize(row);
+                            let clicked = ui
+                                .selectable_config(&command.name)
+                                .selected(row == self.selected)
+                                .build();
+                            if let Some(shortcut) = &command.shortcut {
+                                let shortcut_width = ui.calc_text_size(shortcut)[0];
+                                ui.same_line_with_pos(width - shortcut_width);
+                                ui.text_disabled(shortcut);
+                            }
+                            if clicked {
+                                chosen = Some(self.ranked[row]);
+                            }
+                        }
+                    });
+
+                if entered {
+                    chosen = self.ranked.get(self.selected).copied();
+                }
+                if chosen.is_some() || ui.is_key_pressed(Key::Escape) {
+                    ui.close_current_popup();
+                }
+            });
+        chosen.map(|index| &self.commands[index].value)
+    }
+}
+
+/// Moves the selection with the arrow keys and completes the query with Tab.
+struct Navigation<'a> {
+    selected: &'a mut usize,
+    count: usize,
+    completion: Option<&'a str>,
+    moved: &'a mut bool,
+}
+
+impl InputTextCallbackHandler for Navigation<'_> {
+    fn on_history(&mut self, direction: HistoryDirection, _: TextCallbackData) {
+        if self.count == 0 {
+            return;
+        }
+        *self.selected = match direction {
+            HistoryDirection::Up => self.selected.checked_sub(1).unwrap_or(self.count - 1),
+            HistoryDirection::Down => (*self.selected + 1) % self.count,
+        };
+        *self.moved = true;
+    }
+
+    fn on_completion(&mut self, mut data: TextCallbackData) {
+        if let Some(completion) = self.completion {
+            data.clear();
+            data.push_str(completion);
+        }
+    }
+}
+
+#[test]
+fn test_command_palette_ranking() {
+    let mut palette = CommandPalette::new("palette");
+    palette.add(Command::new("Open file", 1));
+    palette.add(Command::new("Save file", 2).shortcut("Ctrl+S"));
+    palette.add(Command::new("Save all files", 3));
+    let names = |palette: &CommandPalette<i32>| {
+        palette
+            .matches()
+            .map(|command| *command.value())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(&palette), [1, 2, 3]);
+    palette.filter.set_filter("svf");
+    palette.rank();
+    assert_eq!(names(&palette), [2, 3]);
+    palette.filter.set_filter("all");
+    palette.rank();
+    assert_eq!(names(&palette), [3]);
+}
+
+#[test]
+fn test_command_palette_choose() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.io_mut().config_input_trickle_event_queue = false;
+    let mut palette = CommandPalette::new("palette");
+    palette.add(Command::new("Open file", 1));
+    palette.add(Command::new("Save file", 2));
+    palette.open();
+
+    let mut chosen = None;
+    for frame in 0..4 {
+        if frame == 2 {
+            let io = ctx.io_mut();
+            io.add_input_character('s');
+            io.add_key_event(Key::Enter, true);
+        }
+        let ui = ctx.new_frame();
+        chosen = chosen.or(palette.build(ui).copied());
+        ctx.render();
+        ctx.io_mut().add_key_event(Key::Enter, false);
+    }
+    assert_eq!(chosen, Some(2));
+}
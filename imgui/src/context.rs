@@ -10,7 +10,7 @@ use crate::fonts::atlas::{FontAtlas, FontId, SharedFontAtlas};
 use crate::io::Io;
 use crate::style::Style;
 use crate::{sys, DrawData};
-use crate::{MouseCursor, Ui};
+use crate::{MouseCursor, TextBuffer, Ui};
 
 #[cfg(feature = "docking")]
 use crate::docking_utils;
@@ -209,6 +209,16 @@ impl Context {
         let data = unsafe { CStr::from_ptr(sys::igSaveIniSettingsToMemory(ptr::null_mut())) };
         buf.push_str(&data.to_string_lossy());
     }
+    /// Appends settings in .Ini file format to a [`TextBuffer`], without converting them to a
+    /// Rust string first.
+    #[doc(alias = "SaveIniSettingsToMemory")]
+    pub fn save_ini_settings_to_buffer(&mut self, buf: &mut TextBuffer) {
+        let mut size = 0;
+        let data = unsafe { sys::igSaveIniSettingsToMemory(&mut size) };
+        if !data.is_null() {
+            buf.append_bytes(unsafe { std::slice::from_raw_parts(data as *const u8, size) });
+        }
+    }
     /// Sets the clipboard backend used for clipboard operations
     pub fn set_clipboard_backend<T: ClipboardBackend>(&mut self, backend: T) {
         let clipboard_ctx: Box<UnsafeCell<_>> = Box::new(ClipboardContext::new(backend).into());
@@ -270,6 +280,7 @@ impl Drop for Context {
             }
             sys::igDestroyContext(self.raw);
         }
+        crate::storage::clear_item_states(self.raw);
     }
 }
 
@@ -451,6 +462,17 @@ Size=400,400";
     assert_eq!(data.trim(), buf.trim());
 }
 
+#[test]
+fn test_ini_save_to_buffer() {
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    ctx.load_ini_settings("[Window][Debug##Default]\nPos=60,60\nSize=400,400");
+    let mut expected = String::new();
+    ctx.save_ini_settings(&mut expected);
+    let mut buf = TextBuffer::new();
+    ctx.save_ini_settings_to_buffer(&mut buf);
+    assert_eq!(buf.to_str(), Ok(expected.as_str()));
+}
+
 #[test]
 fn test_default_ini_filename() {
     let _guard = crate::test::TEST_MUTEX.lock();
@@ -0,0 +1,450 @@
+use std::mem;
+
+use crate::{TabBarFlags, TabItemFlags, Ui};
+
+/// A document managed by a [`DocumentSet`].
+pub trait Document {
+    /// Returns the label of the tab or window showing the document.
+    ///
+    /// Labels must be unique within a set. Use `###` to keep the id stable when the visible
+    /// part of the label changes.
+    fn label(&self) -> &str;
+
+    /// Returns true if the document has changes which would be lost when closing it.
+    fn is_unsaved(&self) -> bool;
+
+    /// Saves the document when the user chooses to do so before closing it.
+    ///
+    /// Returns false if the document couldn't be saved, in which case it is kept open.
+    fn save(&mut self) -> bool;
+
+    /// Builds the contents of the document.
+    fn draw(&mut self, ui: &Ui);
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum CloseState {
+    Open,
+    /// Waiting for the user to confirm, if the document has unsaved changes.
+    Requested,
+    /// Removed at the start of the next build.
+    Confirmed,
+}
+
+#[derive(Debug)]
+struct Entry<D> {
+    document: D,
+    close: CloseState,
+    select: bool,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum CloseAction {
+    Close(usize),
+    CloseOthers(usize),
+    CloseAll,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Choice {
+    Save,
+    Discard,
+    Cancel,
+}
+
+/// A list of documents shown as tabs (or, with the `docking` feature, as dockable windows),
+/// with "Close", "Close others" and "Close all" in the tab context menu and a confirmation modal
+/// for closing documents with unsaved changes.
+///
+/// ```no_run
+/// # use imgui::*;
+/// struct TextFile {
+///     name: String,
+///     text: String,
+///     unsaved: bool,
+/// }
+///
+/// impl Document for TextFile {
+///     fn label(&self) -> &str {
+///         &self.name
+///     }
+///     fn is_unsaved(&self) -> bool {
+///         self.unsaved
+///     }
+///     fn save(&mut self) -> bool {
+///         self.unsaved = !std::fs::write(&self.name, &self.text).is_ok();
+///         !self.unsaved
+///     }
+///     fn draw(&mut self, ui: &Ui) {
+///         self.unsaved |= ui.input_text_multiline("##text", &mut self.text, [-1.0, -1.0]).build();
+///     }
+/// }
+///
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let mut documents = DocumentSet::new("editor");
+/// documents.add(TextFile { name: "notes.txt".into(), text: String::new(), unsaved: false });
+/// ui.window("Editor").build(|| {
+///     for closed in documents.build(ui) {
+///         println!("closed {}", closed.name);
+///     }
+/// });
+/// ```
+#[derive(Debug)]
+pub struct DocumentSet<D> {
+    id: String,
+    entries: Vec<Entry<D>>,
+    flags: TabBarFlags,
+    confirming: bool,
+}
+
+impl<D: Document> DocumentSet<D> {
+    /// Creates an empty set. `id` is used as the id of its tab bar and confirmation modal.
+    pub fn new(id: impl Into<String>) -> Self {
+        DocumentSet {
+            id: id.into(),
+            entries: Vec::new(),
+            flags: TabBarFlags::REORDERABLE,
+            confirming: false,
+        }
+    }
+
+    /// Sets the flags of the tab bar.
+    ///
+    /// Defaults to [`TabBarFlags::REORDERABLE`].
+    #[inline]
+    pub fn tab_bar_flags(mut self, flags: TabBarFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Adds a document and selects it, returning its index.
+    pub fn add(&mut self, document: D) -> usize {
+        self.entries.push(Entry {
+            document,
+            close: CloseState::Open,
+            select: true,
+        });
+        self.entries.len() - 1
+    }
+
+    /// Returns the number of documents, including those waiting to be closed.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the set contains no documents.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the document at `index`.
+    pub fn get(&self, index: usize) -> Option<&D> {
+        self.entries.get(index).map(|entry| &entry.document)
+    }
+
+    /// Returns the document at `index` mutably.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut D> {
+        self.entries.get_mut(index).map(|entry| &mut entry.document)
+    }
+
+    /// Returns an iterator over the documents.
+    pub fn iter(&self) -> impl Iterator<Item = &D> + '_ {
+        self.entries.iter().map(|entry| &entry.document)
+    }
+
+    /// Returns an iterator over the documents which allows modifying them.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut D> + '_ {
+        self.entries.iter_mut().map(|entry| &mut entry.document)
+    }
+
+    /// Selects the tab (or focuses the window) of the document at `index` on the next build.
+    pub fn select(&mut self, index: usize) {
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.select = true;
+        }
+    }
+
+    /// Requests closing the document at `index`. Unsaved documents are only closed after
+    /// confirmation.
+    pub fn close(&mut self, index: usize) {
+        self.apply(CloseAction::Close(index));
+    }
+
+    /// Requests closing all documents except the one at `index`.
+    pub fn close_others(&mut self, index: usize) {
+        self.apply(CloseAction::CloseOthers(index));
+    }
+
+    /// Requests closing all documents.
+    pub fn close_all(&mut self) {
+        self.apply(CloseAction::CloseAll);
+    }
+
+    /// Returns true if some documents are waiting to be closed, for example for the user to
+    /// confirm discarding their changes.
+    pub fn is_closing(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.close != CloseState::Open)
+    }
+
+    fn apply(&mut self, action: CloseAction) {
+        for (index, entry) in self.entries.iter_mut().enumerate() {
+            let close = match action {
+                CloseAction::Close(target) => index == target,
+                CloseAction::CloseOthers(target) => index != target,
+                CloseAction::CloseAll => true,
+            };
+            if close && entry.close == CloseState::Open {
+                entry.close = CloseState::Requested;
+            }
+        }
+    }
+
+    /// Builds the documents as tabs of a tab bar, followed by the confirmation modal.
+    ///
+    /// Returns the documents which were closed.
+    pub fn build(&mut self, ui: &Ui) -> Vec<D> {
+        let closed = self.remove_closed();
+        if let Some(_tab_bar) = ui.tab_bar_with_flags(&self.id, self.flags) {
+            // Telling Dear ImGui before the tabs are submitted avoids a flicker.
+            for document in &closed {
+                ui.set_tab_item_closed(document.label());
+            }
+            let mut action = None;
+            for (index, entry) in self.entries.iter_mut().enumerate() {
+                let mut flags = TabItemFlags::empty();
+                flags.set(TabItemFlags::UNSAVED_DOCUMENT, entry.document.is_unsaved());
+                flags.set(TabItemFlags::SET_SELECTED, mem::take(&mut entry.select));
+                let mut open = true;
+                let tab = ui.tab_item_with_flags(entry.document.label(), Some(&mut open), flags);
+                if !open && entry.close == CloseState::Open {
+                    entry.close = CloseState::Requested;
+                }
+                if let Some(_popup) = ui.begin_popup_context_item() {
+                    if ui.menu_item("Close") {
+                        action = Some(CloseAction::Close(index));
+                    }
+                    if ui.menu_item("Close others") {
+                        action = Some(CloseAction::CloseOthers(index));
+                    }
+                    if ui.menu_item("Close all") {
+                        action = Some(CloseAction::CloseAll);
+                    }
+                }
+                if tab.is_some() {
+                    entry.document.draw(ui);
+                }
+            }
+            if let Some(action) = action {
+                self.apply(action);
+            }
+        }
+        self.build_confirmation(ui);
+        closed
+    }
+
+    /// Builds each document as a window, which can be docked together, followed by the
+    /// confirmation modal.
+    ///
+    /// Returns the documents which were closed.
+    #[cfg(feature = "docking")]
+    pub fn build_windows(&mut self, ui: &Ui) -> Vec<D> {
+        let closed = self.remove_closed();
+        for entry in &mut self.entries {
+            let mut open = true;
+            let document = &mut entry.document;
+            // The label is copied, as the document is borrowed mutably while building.
+            let label = document.label().to_owned();
+            ui.window(label)
+                .opened(&mut open)
+                .unsaved_document(document.is_unsaved())
+                .focused(mem::take(&mut entry.select))
+                .build(|| document.draw(ui));
+            if !open && entry.close == CloseState::Open {
+                entry.close = CloseState::Requested;
+            }
+        }
+        self.build_confirmation(ui);
+        closed
+    }
+
+    /// Removes the documents which can be closed without confirmation or whose closing was
+    /// confirmed.
+    fn remove_closed(&mut self) -> Vec<D> {
+        let mut closed = Vec::new();
+        let mut index = 0;
+        while index < self.entries.len() {
+            let entry = &self.entries[index];
+            let remove = match entry.close {
+                CloseState::Open => false,
+                CloseState::Requested => !entry.document.is_unsaved(),
+                CloseState::Confirmed => true,
+            };
+            if remove {
+                closed.push(self.entries.remove(index).document);
+            } else {
+                index += 1;
+            }
+        }
+        closed
+    }
+
+    fn build_confirmation(&mut self, ui: &Ui) {
+        let modal_id = format!("Save changes?##{}", self.id);
+        let pending =
+            |entry: &&Entry<D>| entry.close == CloseState::Requested && entry.document.is_unsaved();
+        if !self.confirming && self.entries.iter().any(|entry| pending(&entry)) {
+            ui.open_popup(&modal_id);
+            self.confirming = true;
+        }
+
+        let entries = &self.entries;
+        let choice = ui
+            .modal_popup_config(&modal_id)
+            .always_auto_resize(true)
+            .build(|| {
+                ui.text("Save changes to the following documents?");
+                for entry in entries.iter().filter(pending) {
+                    let label = entry.document.label();
+                    ui.bullet_text(label.split("##").next().unwrap_or(label));
+                }
+                let mut choice = None;
+                if ui.button("Save") {
+                    choice = Some(Choice::Save);
+                }
+                ui.same_line();
+                if ui.button("Discard") {
+                    choice = Some(Choice::Discard);
+                }
+                ui.same_line();
+                if ui.button("Cancel") {
+                    choice = Some(Choice::Cancel);
+                }
+                // Also close when the pending documents were saved or closed in the meantime.
+                if choice.is_some() || !entries.iter().any(|entry| pending(&entry)) {
+                    ui.close_current_popup();
+                }
+                choice
+            })
+            .flatten();
+        if !self.entries.iter().any(|entry| pending(&entry)) {
+            self.confirming = false;
+        }
+
+        if let Some(choice) = choice {
+            self.confirming = false;
+            for entry in &mut self.entries {
+                if entry.close != CloseState::Requested {
+                    continue;
+                }
+                let close = match choice {
+                    Choice::Save => !entry.document.is_unsaved() || entry.document.save(),
+                    Choice::Discard => true,
+                    Choice::Cancel => false,
+                };
+                entry.close = if close {
+                    CloseState::Confirmed
+                } else {
+                    CloseState::Open
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+struct TestDocument {
+    name: &'static str,
+    unsaved: bool,
+}
+
+#[cfg(test)]
+impl Document for TestDocument {
+    fn label(&self) -> &str {
+        self.name
+    }
+    fn is_unsaved(&self) -> bool {
+        self.unsaved
+    }
+    fn save(&mut self) -> bool {
+        self.unsaved = false;
+        true
+    }
+    fn draw(&mut self, ui: &Ui) {
+        ui.text(self.name);
+    }
+}
+
+#[test]
+fn test_document_set_close() {
+    use crate::{ConfigFlags, Key};
+
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.io_mut().config_flags |= ConfigFlags::NAV_ENABLE_KEYBOARD;
+    ctx.io_mut().config_input_trickle_event_queue = false;
+    let mut documents = DocumentSet::new("documents");
+    let document = |name| TestDocument {
+        name,
+        unsaved: true,
+    };
+    documents.add(TestDocument {
+        name: "saved",
+        unsaved: false,
+    });
+    documents.add(document("cancelled"));
+    // Builds a frame, then presses and releases each of `keys` over two frames.
+    let mut build = |documents: &mut DocumentSet<TestDocument>, keys: &[Key]| {
+        let mut closed = Vec::new();
+        let events = keys
+            .iter()
+            .flat_map(|&key| [Some((key, true)), Some((key, false))]);
+        for event in [None].into_iter().chain(events) {
+            if let Some((key, down)) = event {
+                ctx.io_mut().add_key_event(key, down);
+            }
+            let ui = ctx.new_frame();
+            ui.window("editor")
+                .build(|| closed.extend(documents.build(ui)));
+            ctx.render();
+        }
+        closed
+            .into_iter()
+            .map(|doc| (doc.name, doc.unsaved))
+            .collect::<Vec<_>>()
+    };
+
+    assert!(build(&mut documents, &[]).is_empty());
+    documents.close_all();
+    assert_eq!(build(&mut documents, &[]), [("saved", false)]);
+    // The unsaved document waits for confirmation, focused on "Save".
+    assert!(build(&mut documents, &[]).is_empty());
+    assert_eq!(documents.len(), 1);
+    assert!(documents.is_closing());
+
+    // "Cancel" keeps the document open.
+    let keys = [Key::RightArrow, Key::RightArrow, Key::Enter];
+    assert!(build(&mut documents, &keys).is_empty());
+    assert!(!documents.is_closing());
+    assert!(documents.get(0).unwrap().unsaved);
+
+    // "Discard" closes the document without saving it.
+    documents.close(0);
+    build(&mut documents, &[]);
+    let keys = [Key::RightArrow, Key::Enter];
+    assert_eq!(build(&mut documents, &keys), [("cancelled", true)]);
+    assert!(documents.is_empty());
+
+    // "Save" saves the document before closing it.
+    documents.add(document("saved later"));
+    build(&mut documents, &[]);
+    documents.close(0);
+    build(&mut documents, &[]);
+    assert_eq!(
+        build(&mut documents, &[Key::Enter]),
+        [("saved later", false)]
+    );
+    assert!(documents.is_empty());
+    assert!(!documents.is_closing());
+}
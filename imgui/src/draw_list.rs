@@ -19,8 +19,10 @@ use crate::{math::MintVec2, ImColor32};
 use sys::{ImDrawCmd, ImDrawList};
 
 use super::Ui;
+use crate::render::draw_data::{typed_callback, BlendMode, DrawCallback, SamplerFilter};
 use crate::render::renderer::TextureId;
 
+use std::any::Any;
 use std::marker::PhantomData;
 
 bitflags!(
@@ -474,6 +476,49 @@ impl<'ui> DrawListMut<'ui> {
     pub fn add_callback<F: FnOnce() + 'static>(&'ui self, callback: F) -> Callback<'ui, F> {
         Callback::new(self, callback)
     }
+
+    /// Draw the specified callback, which receives a context provided by the renderer.
+    ///
+    /// The renderer sees this callback as a [`DrawCmd::UserCallback`](crate::DrawCmd::UserCallback)
+    /// and runs it with [`UserCallbackHandle::invoke`](crate::UserCallbackHandle::invoke). The
+    /// context can be downcast to whatever type the renderer documents, e.g. its render pass.
+    ///
+    /// Note: if this DrawList is never rendered the callback will leak because DearImGui
+    /// does not provide a method to clean registered callbacks.
+    pub fn add_user_callback<F: FnOnce(&mut dyn Any) + 'static>(
+        &'ui self,
+        callback: F,
+    ) -> UserCallback<'ui, F> {
+        UserCallback::new(self, callback)
+    }
+
+    /// Ask the renderer to switch to the given blend mode for the following draw commands.
+    ///
+    /// The renderer sees this as a [`DrawCallback::SetBlendMode`] marker. Renderers reset to
+    /// [`BlendMode::Alpha`] at the start of each draw list.
+    pub fn set_blend_mode(&self, mode: BlendMode) {
+        unsafe {
+            sys::ImDrawList_AddCallback(
+                self.draw_list,
+                Some(typed_callback),
+                DrawCallback::blend_mode_data(mode),
+            );
+        }
+    }
+
+    /// Ask the renderer to switch to the given texture filtering for the following draw commands.
+    ///
+    /// The renderer sees this as a [`DrawCallback::SetSampler`] marker. Renderers reset to
+    /// [`SamplerFilter::Linear`] at the start of each draw list.
+    pub fn set_sampler(&self, filter: SamplerFilter) {
+        unsafe {
+            sys::ImDrawList_AddCallback(
+                self.draw_list,
+                Some(typed_callback),
+                DrawCallback::sampler_data(filter),
+            );
+        }
+    }
 }
 
 /// Represents a line about to be drawn
@@ -1234,3 +1279,29 @@ impl<'ui, F: FnOnce() + 'static> Callback<'ui, F> {
         callback();
     }
 }
+
+#[must_use = "should call .build() to draw the object"]
+pub struct UserCallback<'ui, F> {
+    draw_list: &'ui DrawListMut<'ui>,
+    callback: F,
+}
+
+impl<'ui, F: FnOnce(&mut dyn Any) + 'static> UserCallback<'ui, F> {
+    /// Typically constructed by [`DrawListMut::add_user_callback`]
+    pub fn new(draw_list: &'ui DrawListMut<'_>, callback: F) -> Self {
+        UserCallback {
+            draw_list,
+            callback,
+        }
+    }
+    /// Adds the callback to the draw-list so it will be run when the window is drawn
+    pub fn build(self) {
+        unsafe {
+            sys::ImDrawList_AddCallback(
+                self.draw_list.draw_list,
+                Some(typed_callback),
+                DrawCallback::user_data(Box::new(self.callback)),
+            );
+        }
+    }
+}
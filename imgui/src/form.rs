@@ -0,0 +1,212 @@
+use crate::style::ERROR_COLOR;
+use crate::Ui;
+
+impl Ui {
+    /// Creates an unbuilt form, see [`Form`].
+    pub fn form<Label: AsRef<str>>(&self, label: Label) -> Form<'_, Label> {
+        Form {
+            label,
+            label_width: None,
+            ui: self,
+        }
+    }
+}
+
+/// Builder for a form: rows of fields with their labels aligned on the left in a column, and
+/// their widgets filling the rest of the width.
+///
+/// The width of the label column is measured as the form is built, and kept under the id of the
+/// label for the next frame.
+///
+/// Each field gets a hidden id (`"##"` followed by its label) to pass to its widget, so the
+/// widget doesn't draw its own label on the right. Fields can have a help tooltip shown next
+/// to the label, and a validation message shown under the widget.
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let mut name = String::new();
+/// let mut port = 8080;
+/// let edited = ui.form("server").build(|form| {
+///     form.field("Name")
+///         .error(name.is_empty().then_some("A name is required"))
+///         .build(|id| ui.input_text(id, &mut name).build());
+///     form.field("Port")
+///         .help("The port the server listens on.")
+///         .build(|id| ui.input_int(id, &mut port).build());
+/// });
+/// for label in edited {
+///     println!("{label} was edited");
+/// }
+/// ```
+#[derive(Copy, Clone, Debug)]
+#[must_use]
+pub struct Form<'ui, Label> {
+    label: Label,
+    label_width: Option<f32>,
+    ui: &'ui Ui,
+}
+
+impl<'ui, Label: AsRef<str>> Form<'ui, Label> {
+    /// Sets the width of the label column.
+    ///
+    /// Defaults to the width of the widest label.
+    #[inline]
+    pub fn label_width(mut self, label_width: f32) -> Self {
+        self.label_width = Some(label_width);
+        self
+    }
+    /// Builds the form, running `f` to add its fields to the given [`FormFields`].
+    ///
+    /// Returns the labels of the fields which were edited, in order.
+    pub fn build(self, f: impl FnOnce(&mut FormFields<'ui>)) -> Vec<String> {
+        let ui = self.ui;
+        let id = ui.new_id_str(self.label.as_ref());
+        let label_width = match self.label_width {
+            Some(label_width) => label_width,
+            None => ui.with_item_state(id, |width: &mut f32| *width),
+        };
+        let _id = ui.push_id(self.label.as_ref());
+        let _group = ui.begin_group();
+        let mut fields = FormFields {
+            ui,
+            start_x: ui.cursor_screen_pos()[0],
+            label_width,
+            measured_width: 0.0,
+            edited: Vec::new(),
+        };
+        f(&mut fields);
+        let measured_width = fields.measured_width;
+        ui.with_item_state(id, |width: &mut f32| *width = measured_width);
+        fields.edited
+    }
+}
+
+/// Adds fields to a [`Form`], in order.
+#[derive(Debug)]
+pub struct FormFields<'ui> {
+    ui: &'ui Ui,
+    start_x: f32,
+    /// Width of the label column, measured in the previous frame unless it was set.
+    label_width: f32,
+    measured_width: f32,
+    edited: Vec<String>,
+}
+
+impl<'ui> FormFields<'ui> {
+    /// Creates an unbuilt field, see [`FormField`].
+    ///
+    /// As with widgets, the part of the label after `"##"` is not shown.
+    pub fn field<'a>(&'a mut self, label: &'a str) -> FormField<'a, 'ui> {
+        FormField {
+            label,
+            help: None,
+            error: None,
+            fields: self,
+        }
+    }
+}
+
+/// Builder for a row of a [`Form`].
+#[derive(Debug)]
+#[must_use]
+pub struct FormField<'a, 'ui> {
+    label: &'a str,
+    help: Option<&'a str>,
+    error: Option<&'a str>,
+    fields: &'a mut FormFields<'ui>,
+}
+
+impl<'a> FormField<'a, '_> {
+    /// Sets a help text shown in a tooltip when hovering a marker next to the label.
+    #[inline]
+    pub fn help(mut self, help: &'a str) -> Self {
+        self.help = Some(help);
+        self
+    }
+    /// Sets a validation message shown under the widget, or `None` if the field is valid.
+    #[inline]
+    pub fn error(mut self, error: Option<&'a str>) -> Self {
+        self.error = error;
+        self
+    }
+    /// Builds the field, running `f` with the hidden id to give to its widget.
+    ///
+    /// `f` returns true if the field was edited, as most widgets do. Returns the result of `f`.
+    pub fn build(self, f: impl FnOnce(&str) -> bool) -> bool {
+        let fields = self.fields;
+        let ui = fields.ui;
+        let visible_label = self.label.split("##").next().unwrap_or_default();
+        let row_y = ui.cursor_screen_pos()[1];
+        ui.align_text_to_frame_padding();
+        ui.text(visible_label);
+        if let Some(help) = self.help {
+            ui.same_line();
+            ui.text_disabled("(?)");
+            if ui.is_item_hovered() {
+                ui.tooltip_text(help);
+            }
+        }
+        let width = ui.item_rect_max()[0] - fields.start_x;
+        fields.measured_width = fields.measured_width.max(width);
+
+        // A label wider than the column of the previous frame pushes its own widget only.
+        let spacing = ui.clone_style().item_spacing[0];
+        let field_x = fields.start_x + fields.label_width.max(width) + spacing;
+        ui.same_line();
+        ui.set_cursor_screen_pos([field_x, row_y]);
+        ui.set_next_item_width(-f32::MIN_POSITIVE);
+        let edited = f(&format!("##{}", self.label));
+        if let Some(error) = self.error {
+            ui.set_cursor_screen_pos([field_x, ui.cursor_screen_pos()[1]]);
+            ui.text_colored(ERROR_COLOR, error);
+        }
+        if edited {
+            fields.edited.push(visible_label.to_owned());
+        }
+        edited
+    }
+}
+
+#[test]
+fn test_form_layout() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let mut name = String::from("imgui");
+    for frame in 0..3 {
+        let ui = ctx.new_frame();
+        ui.window("form")
+            .position([0.0, 0.0], crate::Condition::Always)
+            .size([400.0, 300.0], crate::Condition::Always)
+            .no_decoration()
+            .build(|| {
+                let mut rects = Vec::new();
+                let edited = ui.form("settings").build(|form| {
+                    form.field("Name").build(|id| {
+                        assert_eq!(id, "##Name");
+                        ui.input_text(id, &mut name).build();
+                        rects.push((ui.item_rect_min(), ui.item_rect_max()));
+                        false
+                    });
+                    form.field("Longer label##2")
+                        .help("Help")
+                        .error(Some("Invalid"))
+                        .build(|id| {
+                            ui.button_with_size(id, [-f32::MIN_POSITIVE, 0.0]);
+                            rects.push((ui.item_rect_min(), ui.item_rect_max()));
+                            true
+                        });
+                });
+                assert_eq!(edited, ["Longer label"]);
+                if frame > 0 {
+                    // The widgets start after the widest label and fill the window.
+                    let label_width = ui.calc_text_size("Longer label")[0];
+                    assert_eq!(rects[0].0[0], rects[1].0[0]);
+                    assert!(rects[0].0[0] > 8.0 + label_width);
+                    assert_eq!(rects[0].1[0], 392.0);
+                    assert_eq!(rects[1].1[0], 392.0);
+                }
+            });
+        ctx.render();
+    }
+}
@@ -5,8 +5,11 @@ use std::ptr;
 
 use crate::internal::DataTypeKind;
 use crate::math::*;
+use crate::style::ERROR_COLOR;
+use crate::style::{StyleColor, StyleVar};
 use crate::sys;
-use crate::Ui;
+use crate::widget::scalar::{edit_scalar, scalar_format, Scalar};
+use crate::{Id, Ui};
 
 bitflags!(
     /// Flags for text inputs
@@ -169,9 +172,10 @@ macro_rules! impl_step_params {
 pub struct InputText<'ui, 'p, L, H = &'static str, T = PassthroughCallback> {
     label: L,
     hint: Option<H>,
-    buf: &'p mut String,
+    buf: &'p mut dyn TextBufferLike,
     callback_handler: T,
     flags: InputTextFlags,
+    validation: Validation<'p, dyn TextBufferLike + 'p>,
     ui: &'ui Ui,
 }
 
@@ -180,17 +184,18 @@ impl<'ui, 'p, L: AsRef<str>> InputText<'ui, 'p, L> {
     ///
     /// # String Editing
     ///
-    /// Please note, ImGui requires this string to be null-terminated. We accomplish this
-    /// by appending and then removing a null terminator (`\0`) from the String you pass in.
+    /// Please note, ImGui requires this text to be null-terminated. We accomplish this by
+    /// reserving one more byte in the [`TextBufferLike`] you pass in and writing a null
+    /// terminator (`\0`) into it, right after the text. The length of the buffer is unchanged.
     /// This has several consequences:
-    /// 1. The string's backing buffer may be resized and relocated even without edits as result
-    ///    of this pushed char.
-    /// 2. **The string will appear truncated if the string contains `\0` inside it.** This will not
+    /// 1. The buffer may be resized and relocated even without edits, to make room for the
+    ///    terminator.
+    /// 2. **The text will appear truncated if it contains `\0` inside it.** This will not
     ///    cause memory *unsafety*, but it will limit your usage. If that's the case, please pre-process
     ///    your string.
     /// 3. Truncations by ImGui appear to be done primarily by insertions of `\0` to the truncation point.
     ///    We will handle this for you and edit the string "properly" too, but this might show up in callbacks.
-    pub fn new(ui: &'ui Ui, label: L, buf: &'p mut String) -> Self {
+    pub fn new(ui: &'ui Ui, label: L, buf: &'p mut impl TextBufferLike) -> Self {
         InputText {
             label,
             hint: None,
@@ -198,6 +203,7 @@ impl<'ui, 'p, L: AsRef<str>> InputText<'ui, 'p, L> {
             callback_handler: PassthroughCallback,
             buf,
             flags: InputTextFlags::CALLBACK_RESIZE,
+            validation: Validation::default(),
             ui,
         }
     }
@@ -218,6 +224,7 @@ where
             buf: self.buf,
             callback_handler: self.callback_handler,
             flags: self.flags,
+            validation: self.validation,
             ui: self.ui,
         }
     }
@@ -268,90 +275,160 @@ where
             hint: self.hint,
             buf: self.buf,
             flags: self.flags,
+            validation: self.validation,
             ui: self.ui,
         }
     }
 
+    /// Sets a validator checking the text, which returns an error message if the text is
+    /// invalid.
+    ///
+    /// While the text is invalid, the frame of the input has a red border and the error is shown
+    /// in a tooltip when hovering it. The initial text is only flagged once it has been edited.
+    ///
+    /// ```no_run
+    /// # let mut ctx = imgui::Context::create();
+    /// # let ui = ctx.frame();
+    /// let mut email = String::new();
+    /// ui.input_text("Email", &mut email)
+    ///     .validate(|text| match text.contains('@') {
+    ///         true => Ok(()),
+    ///         false => Err("Not an email address".to_owned()),
+    ///     })
+    ///     .revert_invalid(true)
+    ///     .build();
+    /// ```
+    #[inline]
+    pub fn validate(mut self, validator: impl FnMut(&str) -> Result<(), String> + 'p) -> Self {
+        self.validation.validator = Some(Box::new(TextValidator(validator)));
+        self
+    }
+
+    /// Sets when the validator runs.
+    ///
+    /// Defaults to [`ValidationTrigger::Edit`].
+    #[inline]
+    pub fn validate_on(mut self, trigger: ValidationTrigger) -> Self {
+        self.validation.trigger = trigger;
+        self
+    }
+
+    /// Restores the last valid text when the input is deactivated with an invalid text.
+    ///
+    /// The text is not restored while it is being edited. Defaults to false.
+    #[inline]
+    pub fn revert_invalid(mut self, value: bool) -> Self {
+        self.validation.revert_invalid = value;
+        self
+    }
+
     /// Builds the string editor, performing string editing operations.
     ///
     /// # String Editing
     ///
-    /// Please note, ImGui requires this string to be null-terminated. We accomplish this
-    /// by appending and then removing a null terminator (`\0`) from the String you pass in.
+    /// Please note, ImGui requires this text to be null-terminated. We accomplish this by
+    /// reserving one more byte in the [`TextBufferLike`] you pass in and writing a null
+    /// terminator (`\0`) into it, right after the text. The length of the buffer is unchanged.
     /// This has several consequences:
-    /// 1. The string's backing buffer may be resized and relocated even without edits as result
-    ///    of this pushed char.
-    /// 2. **The string will appear truncated if the string contains `\0` inside it.** This will not
+    /// 1. The buffer may be resized and relocated even without edits, to make room for the
+    ///    terminator.
+    /// 2. **The text will appear truncated if it contains `\0` inside it.** This will not
     ///    cause memory *unsafety*, but it will limit your usage. If that's the case, please pre-process
     ///    your string.
     /// 3. Truncations by ImGui appear to be done primarily by insertions of `\0` to the truncation point.
     ///    We will handle this for you and edit the string "properly" too, but this might show up in callbacks.
     pub fn build(self) -> bool {
-        // needs to be null-terminated! this is a hack!
-        self.buf.push('\0');
-
-        let (ptr, capacity) = (self.buf.as_mut_ptr(), self.buf.capacity());
-
-        let mut data = UserData {
-            container: self.buf,
-            cback_handler: self.callback_handler,
-        };
-        let data = &mut data as *mut _ as *mut c_void;
-
-        let o = unsafe {
-            if let Some(hint) = self.hint {
-                let (label, hint) = self.ui.scratch_txt_two(self.label, hint);
-                sys::igInputTextWithHint(
-                    label,
-                    hint,
-                    ptr as *mut core::ffi::c_char,
-                    capacity,
-                    self.flags.bits() as i32,
-                    Some(callback::<T>),
-                    data,
-                )
-            } else {
-                let label = self.ui.scratch_txt(self.label);
+        let InputText {
+            label,
+            hint,
+            buf,
+            callback_handler,
+            flags,
+            validation,
+            ui,
+        } = self;
+        validation.build(ui, label.as_ref(), buf, |buf| {
+            input_text(ui, label.as_ref(), hint, buf, callback_handler, flags)
+        })
+    }
+}
 
-                sys::igInputText(
-                    label,
-                    ptr as *mut core::ffi::c_char,
-                    capacity,
-                    self.flags.bits() as i32,
-                    Some(callback::<T>),
-                    data,
-                )
-            }
-        };
+fn input_text<T: InputTextCallbackHandler>(
+    ui: &Ui,
+    label: &str,
+    hint: Option<impl AsRef<str>>,
+    buf: &mut dyn TextBufferLike,
+    callback_handler: T,
+    flags: InputTextFlags,
+) -> bool {
+    // needs to be null-terminated! this is a hack!
+    buf.reserve(1);
+    unsafe {
+        let len = buf.len();
+        *buf.as_mut_ptr().add(len) = b'\0';
+    }
 
-        let cap = self.buf.capacity();
+    let (ptr, capacity) = (buf.as_mut_ptr(), buf.capacity());
 
-        // SAFETY: this slice is simply a view into the underlying buffer
-        // of a String. We MAY be holding onto a view of uninitialized memory,
-        // however, since we're holding this as a u8 slice, I think it should be
-        // alright...
-        // additionally, we can go over the bytes directly, rather than char indices,
-        // because NUL will never appear in any UTF8 outside the NUL character (ie, within
-        // a char).
-        let buf = unsafe { std::slice::from_raw_parts(self.buf.as_ptr(), cap) };
-        if let Some(len) = buf.iter().position(|x| *x == b'\0') {
-            // `len` is the position of the first `\0` byte in the String
-            unsafe {
-                self.buf.as_mut_vec().set_len(len);
-            }
+    let mut data = UserData {
+        container: buf,
+        cback_handler: callback_handler,
+    };
+    let data = &mut data as *mut _ as *mut c_void;
+
+    let o = unsafe {
+        if let Some(hint) = hint {
+            let (label, hint) = ui.scratch_txt_two(label, hint);
+            sys::igInputTextWithHint(
+                label,
+                hint,
+                ptr as *mut core::ffi::c_char,
+                capacity,
+                flags.bits() as i32,
+                Some(callback::<T>),
+                data,
+            )
         } else {
-            // There is no null terminator, the best we can do is to not
-            // update the string length.
+            let label = ui.scratch_txt(label);
+
+            sys::igInputText(
+                label,
+                ptr as *mut core::ffi::c_char,
+                capacity,
+                flags.bits() as i32,
+                Some(callback::<T>),
+                data,
+            )
         }
+    };
 
-        o
+    let cap = buf.capacity();
+
+    // SAFETY: this slice is simply a view into the underlying buffer
+    // of a String. We MAY be holding onto a view of uninitialized memory,
+    // however, since we're holding this as a u8 slice, I think it should be
+    // alright...
+    // additionally, we can go over the bytes directly, rather than char indices,
+    // because NUL will never appear in any UTF8 outside the NUL character (ie, within
+    // a char).
+    let bytes = unsafe { std::slice::from_raw_parts(buf.as_mut_ptr(), cap) };
+    if let Some(len) = bytes.iter().position(|x| *x == b'\0') {
+        // `len` is the position of the first `\0` byte in the String
+        unsafe {
+            buf.set_len(len);
+        }
+    } else {
+        // There is no null terminator, the best we can do is to not
+        // update the string length.
     }
+
+    o
 }
 
 #[must_use]
 pub struct InputTextMultiline<'ui, 'p, L, T = PassthroughCallback> {
     label: L,
-    buf: &'p mut String,
+    buf: &'p mut dyn TextBufferLike,
     flags: InputTextFlags,
     size: [f32; 2],
     callback_handler: T,
@@ -363,17 +440,23 @@ impl<'ui, 'p, L: AsRef<str>> InputTextMultiline<'ui, 'p, L, PassthroughCallback>
     ///
     /// # String Editing
     ///
-    /// Please note, ImGui requires this string to be null-terminated. We accomplish this
-    /// by appending and then removing a null terminator (`\0`) from the String you pass in.
+    /// Please note, ImGui requires this text to be null-terminated. We accomplish this by
+    /// reserving one more byte in the [`TextBufferLike`] you pass in and writing a null
+    /// terminator (`\0`) into it, right after the text. The length of the buffer is unchanged.
     /// This has several consequences:
-    /// 1. The string's backing buffer may be resized and relocated even without edits as result
-    ///    of this pushed char.
-    /// 2. **The string will appear truncated if the string contains `\0` inside it.** This will not
+    /// 1. The buffer may be resized and relocated even without edits, to make room for the
+    ///    terminator.
+    /// 2. **The text will appear truncated if it contains `\0` inside it.** This will not
     ///    cause memory *unsafety*, but it will limit your usage. If that's the case, please pre-process
     ///    your string.
     /// 3. Truncations by ImGui appear to be done primarily by insertions of `\0` to the truncation point.
     ///    We will handle this for you and edit the string "properly" too, but this might show up in callbacks.
-    pub fn new(ui: &'ui Ui, label: L, buf: &'p mut String, size: impl Into<MintVec2>) -> Self {
+    pub fn new(
+        ui: &'ui Ui,
+        label: L,
+        buf: &'p mut impl TextBufferLike,
+        size: impl Into<MintVec2>,
+    ) -> Self {
         InputTextMultiline {
             label,
             buf,
@@ -437,19 +520,24 @@ impl<'ui, 'p, T: InputTextCallbackHandler, L: AsRef<str>> InputTextMultiline<'ui
     ///
     /// # String Editing
     ///
-    /// Please note, ImGui requires this string to be null-terminated. We accomplish this
-    /// by appending and then removing a null terminator (`\0`) from the String you pass in.
+    /// Please note, ImGui requires this text to be null-terminated. We accomplish this by
+    /// reserving one more byte in the [`TextBufferLike`] you pass in and writing a null
+    /// terminator (`\0`) into it, right after the text. The length of the buffer is unchanged.
     /// This has several consequences:
-    /// 1. The string's backing buffer may be resized and relocated even without edits as result
-    ///    of this pushed char.
-    /// 2. **The string will appear truncated if the string contains `\0` inside it.** This will not
+    /// 1. The buffer may be resized and relocated even without edits, to make room for the
+    ///    terminator.
+    /// 2. **The text will appear truncated if it contains `\0` inside it.** This will not
     ///    cause memory *unsafety*, but it will limit your usage. If that's the case, please pre-process
     ///    your string.
     /// 3. Truncations by ImGui appear to be done primarily by insertions of `\0` to the truncation point.
     ///    We will handle this for you and edit the string "properly" too, but this might show up in callbacks.
     pub fn build(self) -> bool {
         // needs to be null-terminated! this is a hack!
-        self.buf.push('\0');
+        self.buf.reserve(1);
+        unsafe {
+            let len = self.buf.len();
+            *self.buf.as_mut_ptr().add(len) = b'\0';
+        }
         let (ptr, capacity) = (self.buf.as_mut_ptr(), self.buf.capacity());
 
         let mut data = UserData {
@@ -479,11 +567,11 @@ impl<'ui, 'p, T: InputTextCallbackHandler, L: AsRef<str>> InputTextMultiline<'ui
         // additionally, we can go over the bytes directly, rather than char indices,
         // because NUL will never appear in any UTF8 outside the NUL character (ie, within
         // a char).
-        let buf = unsafe { std::slice::from_raw_parts(self.buf.as_ptr(), cap) };
+        let buf = unsafe { std::slice::from_raw_parts(self.buf.as_mut_ptr(), cap) };
         if let Some(len) = buf.iter().position(|x| *x == b'\0') {
             // `len` is the position of the first `\0` byte in the String
             unsafe {
-                self.buf.as_mut_vec().set_len(len);
+                self.buf.set_len(len);
             }
         } else {
             // There is no null terminator, the best we can do is to not
@@ -729,10 +817,11 @@ pub struct InputScalar<'ui, 'p, T, L, F = &'static str> {
     step_fast: Option<T>,
     display_format: Option<F>,
     flags: InputTextFlags,
+    validation: Validation<'p, T>,
     ui: &'ui Ui,
 }
 
-impl<'ui, 'p, L: AsRef<str>, T: DataTypeKind> InputScalar<'ui, 'p, T, L> {
+impl<'ui, 'p, L: AsRef<str>, T: Scalar> InputScalar<'ui, 'p, T, L> {
     /// Constructs a new input scalar builder.
     #[doc(alias = "InputScalar", alias = "InputScalarN")]
     pub fn new(ui: &'ui Ui, label: L, value: &'p mut T) -> Self {
@@ -743,12 +832,13 @@ impl<'ui, 'p, L: AsRef<str>, T: DataTypeKind> InputScalar<'ui, 'p, T, L> {
             step_fast: None,
             display_format: None,
             flags: InputTextFlags::empty(),
+            validation: Validation::default(),
             ui,
         }
     }
 }
 
-impl<'ui, 'p, L: AsRef<str>, T: DataTypeKind, F: AsRef<str>> InputScalar<'ui, 'p, T, L, F> {
+impl<'ui, 'p, L: AsRef<str>, T: Scalar, F: AsRef<str>> InputScalar<'ui, 'p, T, L, F> {
     /// Sets the display format using *a C-style printf string*
     pub fn display_format<F2: AsRef<str>>(
         self,
@@ -761,6 +851,7 @@ impl<'ui, 'p, L: AsRef<str>, T: DataTypeKind, F: AsRef<str>> InputScalar<'ui, 'p
             step_fast: self.step_fast,
             display_format: Some(display_format),
             flags: self.flags,
+            validation: self.validation,
             ui: self.ui,
         }
     }
@@ -768,27 +859,77 @@ impl<'ui, 'p, L: AsRef<str>, T: DataTypeKind, F: AsRef<str>> InputScalar<'ui, 'p
     ///
     /// Returns true if the value was changed.
     pub fn build(self) -> bool {
-        unsafe {
-            let (one, two) = self
-                .ui
-                .scratch_txt_with_opt(self.label, self.display_format);
+        let step = self.step.map(T::to_repr);
+        let step_fast = self.step_fast.map(T::to_repr);
+        let ui = self.ui;
+        let label = self.label.as_ref();
+        self.validation.build(ui, label, self.value, |value| {
+            let (one, two) =
+                ui.scratch_txt_with_opt(label, scalar_format::<T>(&self.display_format));
+            edit_scalar(value, |value| unsafe {
+                sys::igInputScalar(
+                    one,
+                    T::Repr::KIND as i32,
+                    value,
+                    step.as_ref()
+                        .map(|step| step as *const T::Repr)
+                        .unwrap_or(ptr::null()) as *const c_void,
+                    step_fast
+                        .as_ref()
+                        .map(|step| step as *const T::Repr)
+                        .unwrap_or(ptr::null()) as *const c_void,
+                    two,
+                    self.flags.bits() as i32,
+                )
+            })
+        })
+    }
 
-            sys::igInputScalar(
-                one,
-                T::KIND as i32,
-                self.value as *mut T as *mut c_void,
-                self.step
-                    .as_ref()
-                    .map(|step| step as *const T)
-                    .unwrap_or(ptr::null()) as *const c_void,
-                self.step_fast
-                    .as_ref()
-                    .map(|step| step as *const T)
-                    .unwrap_or(ptr::null()) as *const c_void,
-                two,
-                self.flags.bits() as i32,
-            )
-        }
+    /// Sets a validator checking the value, which returns an error message if the value is
+    /// invalid.
+    ///
+    /// While the value is invalid, the frame of the input has a red border and the error is
+    /// shown in a tooltip when hovering it. The initial value is only flagged once it has been
+    /// edited.
+    ///
+    /// ```no_run
+    /// # let mut ctx = imgui::Context::create();
+    /// # let ui = ctx.frame();
+    /// let mut port: u16 = 8080;
+    /// ui.input_scalar("Port", &mut port)
+    ///     .validate(|port| match *port >= 1024 {
+    ///         true => Ok(()),
+    ///         false => Err("Ports below 1024 are reserved".to_owned()),
+    ///     })
+    ///     .validate_on(imgui::ValidationTrigger::DeactivationAfterEdit)
+    ///     .revert_invalid(true)
+    ///     .build();
+    /// ```
+    #[inline]
+    pub fn validate(mut self, validator: impl FnMut(&T) -> Result<(), String> + 'p) -> Self
+    where
+        T: 'static,
+    {
+        self.validation.validator = Some(Box::new(ScalarValidator(validator)));
+        self
+    }
+
+    /// Sets when the validator runs.
+    ///
+    /// Defaults to [`ValidationTrigger::Edit`].
+    #[inline]
+    pub fn validate_on(mut self, trigger: ValidationTrigger) -> Self {
+        self.validation.trigger = trigger;
+        self
+    }
+
+    /// Restores the last valid value when the input is deactivated with an invalid value.
+    ///
+    /// The value is not restored while it is being edited. Defaults to false.
+    #[inline]
+    pub fn revert_invalid(mut self, value: bool) -> Self {
+        self.validation.revert_invalid = value;
+        self
     }
 
     #[inline]
@@ -806,6 +947,170 @@ impl<'ui, 'p, L: AsRef<str>, T: DataTypeKind, F: AsRef<str>> InputScalar<'ui, 'p
     impl_text_flags!(InputScalar);
 }
 
+/// When the validator of an input runs, see [`InputText::validate`] and
+/// [`InputScalar::validate`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ValidationTrigger {
+    /// Validates the value every time it is edited.
+    #[default]
+    Edit,
+    /// Validates the value once the input is deactivated after being edited, for example when
+    /// Enter is pressed or the input loses focus.
+    DeactivationAfterEdit,
+}
+
+/// The validator of an input editing a `V`, and its options.
+struct Validation<'p, V: ?Sized> {
+    validator: Option<Box<dyn Validator<V> + 'p>>,
+    trigger: ValidationTrigger,
+    revert_invalid: bool,
+}
+
+impl<V: ?Sized> Default for Validation<'_, V> {
+    fn default() -> Self {
+        Validation {
+            validator: None,
+            trigger: ValidationTrigger::Edit,
+            revert_invalid: false,
+        }
+    }
+}
+
+/// The validation state of an input, kept across frames.
+#[derive(Default)]
+struct ValidationState {
+    checked: bool,
+    error: Option<String>,
+}
+
+impl<V: ?Sized> Validation<'_, V> {
+    /// Builds an input editing `value` with `build`, and validates the value.
+    fn build(
+        self,
+        ui: &Ui,
+        label: &str,
+        value: &mut V,
+        build: impl FnOnce(&mut V) -> bool,
+    ) -> bool {
+        let Some(mut validator) = self.validator else {
+            return build(value);
+        };
+        let id = ui.new_id_str(label);
+
+        let invalid = ui.with_item_state(id, |state: &mut ValidationState| state.error.is_some());
+        let style = invalid.then(|| {
+            let border_size = ui.clone_style().frame_border_size.max(1.0);
+            (
+                ui.push_style_color(StyleColor::Border, ERROR_COLOR),
+                ui.push_style_var(StyleVar::FrameBorderSize(border_size)),
+            )
+        });
+        let mut changed = build(value);
+        drop(style);
+
+        let check = match self.trigger {
+            ValidationTrigger::Edit => changed,
+            ValidationTrigger::DeactivationAfterEdit => ui.is_item_deactivated_after_edit(),
+        };
+        let (deactivated, hovered) = (ui.is_item_deactivated(), ui.is_item_hovered());
+        ui.with_item_state(id, |state: &mut ValidationState| {
+            // The initial value is checked too, so there is a valid value to revert to, but it
+            // is only flagged as invalid once the user edits it.
+            let initial = !std::mem::replace(&mut state.checked, true);
+            if check || initial {
+                let result = validator.check(value);
+                if result.is_ok() {
+                    validator.save(ui, id, value);
+                }
+                if check {
+                    state.error = result.err();
+                }
+            }
+            if self.revert_invalid
+                && deactivated
+                && state.error.is_some()
+                && validator.restore(ui, id, value)
+            {
+                state.error = None;
+                changed = true;
+            }
+            if let Some(error) = state.error.as_deref().filter(|_| hovered) {
+                ui.tooltip_text(error);
+            }
+        });
+        changed
+    }
+}
+
+/// Checks the values of an input editing a `V`, and keeps the last valid value.
+trait Validator<V: ?Sized> {
+    /// Returns the error message if `value` is invalid.
+    fn check(&mut self, value: &mut V) -> Result<(), String>;
+    /// Keeps `value` as the last valid value of the input `id`.
+    fn save(&self, ui: &Ui, id: Id, value: &mut V);
+    /// Restores the last valid value of the input `id`, returning false if there is none.
+    fn restore(&self, ui: &Ui, id: Id, value: &mut V) -> bool;
+}
+
+struct TextValidator<F>(F);
+
+impl<'b, F: FnMut(&str) -> Result<(), String>> Validator<dyn TextBufferLike + 'b>
+    for TextValidator<F>
+{
+    fn check(&mut self, buf: &mut (dyn TextBufferLike + 'b)) -> Result<(), String> {
+        (self.0)(&text_buffer_contents(buf))
+    }
+    fn save(&self, ui: &Ui, id: Id, buf: &mut (dyn TextBufferLike + 'b)) {
+        let text = text_buffer_contents(buf).into_owned();
+        ui.with_item_state(id, |last_valid: &mut Option<String>| {
+            *last_valid = Some(text)
+        });
+    }
+    fn restore(&self, ui: &Ui, id: Id, buf: &mut (dyn TextBufferLike + 'b)) -> bool {
+        ui.with_item_state(id, |last_valid: &mut Option<String>| match last_valid {
+            Some(text) => {
+                set_text_buffer_contents(buf, text);
+                true
+            }
+            None => false,
+        })
+    }
+}
+
+struct ScalarValidator<F>(F);
+
+impl<T: Scalar + 'static, F: FnMut(&T) -> Result<(), String>> Validator<T> for ScalarValidator<F> {
+    fn check(&mut self, value: &mut T) -> Result<(), String> {
+        (self.0)(value)
+    }
+    fn save(&self, ui: &Ui, id: Id, value: &mut T) {
+        ui.with_item_state(id, |last_valid: &mut Option<T>| *last_valid = Some(*value));
+    }
+    fn restore(&self, ui: &Ui, id: Id, value: &mut T) -> bool {
+        ui.with_item_state(id, |last_valid: &mut Option<T>| {
+            last_valid.map(|last_valid| *value = last_valid).is_some()
+        })
+    }
+}
+
+fn text_buffer_contents(buf: &mut dyn TextBufferLike) -> std::borrow::Cow<'_, str> {
+    let len = buf.len();
+    // SAFETY: the first `len` bytes of the buffer are its contents
+    let bytes = unsafe { std::slice::from_raw_parts(buf.as_mut_ptr(), len) };
+    String::from_utf8_lossy(bytes)
+}
+
+fn set_text_buffer_contents(buf: &mut dyn TextBufferLike, text: &str) {
+    // Room for the nul terminator, which some buffers expect after the text.
+    buf.reserve((text.len() + 1).saturating_sub(buf.len()));
+    unsafe {
+        let ptr = buf.as_mut_ptr();
+        ptr::copy_nonoverlapping(text.as_ptr(), ptr, text.len());
+        *ptr.add(text.len()) = b'\0';
+        buf.set_len(text.len());
+    }
+}
+
 /// Builder for an input scalar widget.
 #[must_use]
 pub struct InputScalarN<'ui, 'p, T, L, F = &'static str> {
@@ -992,13 +1297,20 @@ impl TextCallbackData {
     }
 
     /// Get a reference to the text callback buffer's str.
+    ///
+    /// Dear ImGui only writes UTF-8, but a `Vec<u8>` buffer may start out holding invalid UTF-8.
+    /// In that case the text is cut at the first invalid byte.
     pub fn str(&self) -> &str {
-        unsafe {
-            std::str::from_utf8(std::slice::from_raw_parts(
-                (*(self.0)).Buf as *const _,
+        let bytes = unsafe {
+            std::slice::from_raw_parts(
+                (*(self.0)).Buf as *const u8,
                 (*(self.0)).BufTextLen as usize,
-            ))
-            .expect("internal imgui error -- it boofed a utf8")
+            )
+        };
+        match std::str::from_utf8(bytes) {
+            Ok(text) => text,
+            // The prefix is valid UTF-8, so this can't fail.
+            Err(err) => std::str::from_utf8(&bytes[..err.valid_up_to()]).unwrap_or_default(),
         }
     }
 
@@ -1017,18 +1329,15 @@ impl TextCallbackData {
     /// This function should have highly limited usage, but could be for
     /// editing certain characters in the buffer based on some external condition.
     ///
+    /// Unlike [str], the whole text is returned even if a `Vec<u8>` buffer holds invalid UTF-8.
+    ///
+    /// [str]: Self::str
     /// [remove_chars]: Self::remove_chars
     /// [set_dirty]: Self::set_dirty
     /// [insert_chars]: Self::insert_chars
     /// [push_str]: Self::push_str
     pub unsafe fn str_as_bytes_mut(&mut self) -> &mut [u8] {
-        let str = std::str::from_utf8_mut(std::slice::from_raw_parts_mut(
-            (*(self.0)).Buf as *const _ as *mut _,
-            (*(self.0)).BufTextLen as usize,
-        ))
-        .expect("internal imgui error -- it boofed a utf8");
-
-        str.as_bytes_mut()
+        std::slice::from_raw_parts_mut((*(self.0)).Buf as *mut u8, (*(self.0)).BufTextLen as usize)
     }
 
     /// Sets the dirty flag on the text to imgui, indicating that
@@ -1196,11 +1505,215 @@ impl TextCallbackData {
     pub fn selection_end_mut(&mut self) -> &mut i32 {
         unsafe { &mut (*self.0).SelectionEnd }
     }
+
+    /// Replaces the text in the given byte range with `s`. If this would
+    /// require the String to resize, it will be resized automatically.
+    ///
+    /// The cursor is placed after the inserted text and the selection is cleared.
+    ///
+    /// ## Panics
+    /// Panics if the start or end of `range` is not a char boundary.
+    pub fn replace_range(&mut self, range: Range<usize>, s: &str) {
+        let text = self.str();
+        assert!(range.start <= range.end);
+        assert!(text.is_char_boundary(range.start) && text.is_char_boundary(range.end));
+        unsafe {
+            self.remove_chars_unchecked(range.start, range.len());
+            self.insert_chars_unsafe(range.start, s);
+        }
+        self.set_cursor_pos(range.start + s.len());
+        self.clear_selection();
+    }
+
+    /// Inserts `s` at the cursor, replacing the selected text if there is any,
+    /// as if it was typed by the user.
+    pub fn insert_at_cursor(&mut self, s: &str) {
+        let range = if self.has_selection() {
+            self.selection()
+        } else {
+            let cursor = self.cursor_pos();
+            cursor..cursor
+        };
+        self.replace_range(range, s);
+    }
+
+    /// Returns the cursor position as a char index, rather than the byte
+    /// position returned by [cursor_pos](Self::cursor_pos).
+    pub fn cursor_char_index(&self) -> usize {
+        self.char_index(self.cursor_pos())
+    }
+
+    /// Sets the cursor position to the given char index. Indices past the
+    /// end of the text place the cursor at the end.
+    pub fn set_cursor_char_index(&mut self, index: usize) {
+        let pos = self.byte_pos(index);
+        self.set_cursor_pos(pos);
+    }
+
+    /// Moves the cursor by the given number of chars, backwards if `chars` is
+    /// negative, and clears the selection. The cursor stops at either end of the text.
+    pub fn move_cursor_chars(&mut self, chars: isize) {
+        let index = self.cursor_char_index().saturating_add_signed(chars);
+        self.set_cursor_char_index(index);
+        self.clear_selection();
+    }
+
+    /// Selects the given byte range and moves the cursor to its end.
+    ///
+    /// ## Panics
+    /// Panics if the start or end of `range` is not a char boundary.
+    pub fn set_selection(&mut self, range: Range<usize>) {
+        let text = self.str();
+        assert!(text.is_char_boundary(range.start) && text.is_char_boundary(range.end));
+        unsafe {
+            (*self.0).SelectionStart = range.start as i32;
+            (*self.0).SelectionEnd = range.end as i32;
+        }
+        self.set_cursor_pos(range.end);
+    }
+
+    /// Gets the range of the selected text in char indices, rather than the
+    /// byte positions returned by [selection](Self::selection).
+    pub fn selection_chars(&self) -> Range<usize> {
+        let selection = self.selection();
+        self.char_index(selection.start)..self.char_index(selection.end)
+    }
+
+    /// Selects the given range of char indices and moves the cursor to its end.
+    /// Indices past the end of the text are clamped to the end.
+    pub fn set_selection_chars(&mut self, range: Range<usize>) {
+        let range = self.byte_pos(range.start)..self.byte_pos(range.end);
+        self.set_selection(range);
+    }
+
+    /// Converts a byte position into a char index.
+    fn char_index(&self, pos: usize) -> usize {
+        self.str()[..pos].chars().count()
+    }
+
+    /// Converts a char index into a byte position, clamped to the end of the text.
+    fn byte_pos(&self, index: usize) -> usize {
+        let text = self.str();
+        text.char_indices()
+            .nth(index)
+            .map_or(text.len(), |(pos, _)| pos)
+    }
+}
+
+/// A growable text buffer which [InputText] and [InputTextMultiline] can edit
+/// in place, resizing it whenever Dear ImGui needs more room (this is what
+/// `ImGuiInputTextFlags_CallbackResize` is used for).
+///
+/// It is implemented for `String`, `Vec<u8>` and [TextBuffer](crate::TextBuffer).
+/// Dear ImGui only ever writes UTF-8 into the buffer.
+///
+/// ## Safety
+///
+/// Implementors must uphold the following invariants:
+/// 1. [as_mut_ptr](Self::as_mut_ptr) points to an allocation of at least
+///    [capacity](Self::capacity) writable bytes, which stays valid until the
+///    buffer is reserved again or dropped.
+/// 2. After [reserve](Self::reserve), the capacity is at least `len() + additional`
+///    and the first `len()` bytes are preserved.
+/// 3. After [set_len](Self::set_len), the first `len` bytes written through
+///    [as_mut_ptr](Self::as_mut_ptr) are the contents of the buffer.
+pub unsafe trait TextBufferLike {
+    /// Returns the length of the text in bytes.
+    fn len(&self) -> usize;
+
+    /// Returns true if the buffer contains no text.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of bytes the buffer can hold without reallocating.
+    fn capacity(&self) -> usize;
+
+    /// Reserves capacity for at least `additional` more bytes.
+    fn reserve(&mut self, additional: usize);
+
+    /// Returns a pointer to the start of the buffer.
+    fn as_mut_ptr(&mut self) -> *mut u8;
+
+    /// Sets the length of the text after Dear ImGui edited the buffer.
+    ///
+    /// ## Safety
+    ///
+    /// `len` must not exceed the capacity, and the first `len` bytes must be
+    /// initialized valid UTF-8.
+    unsafe fn set_len(&mut self, len: usize);
+}
+
+unsafe impl TextBufferLike for String {
+    fn len(&self) -> usize {
+        String::len(self)
+    }
+
+    fn capacity(&self) -> usize {
+        String::capacity(self)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        String::reserve(self, additional)
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut u8 {
+        str::as_mut_ptr(self)
+    }
+
+    unsafe fn set_len(&mut self, len: usize) {
+        self.as_mut_vec().set_len(len)
+    }
+}
+
+unsafe impl TextBufferLike for Vec<u8> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn capacity(&self) -> usize {
+        Vec::capacity(self)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        Vec::reserve(self, additional)
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut u8 {
+        Vec::as_mut_ptr(self)
+    }
+
+    unsafe fn set_len(&mut self, len: usize) {
+        Vec::set_len(self, len)
+    }
+}
+
+unsafe impl TextBufferLike for crate::TextBuffer {
+    fn len(&self) -> usize {
+        crate::TextBuffer::len(self)
+    }
+
+    fn capacity(&self) -> usize {
+        self.raw().Buf.Capacity as usize
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        crate::TextBuffer::reserve(self, additional)
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.raw().Buf.Data as *mut u8
+    }
+
+    unsafe fn set_len(&mut self, len: usize) {
+        // `Buf` holds the text followed by the nul terminator Dear ImGui wrote
+        self.raw_mut().Buf.Size = len as i32 + 1;
+    }
 }
 
 #[repr(C)]
-struct UserData<T> {
-    container: *mut String,
+struct UserData<'p, T> {
+    container: *mut (dyn TextBufferLike + 'p),
     cback_handler: T,
 }
 
@@ -1210,13 +1723,13 @@ extern "C" fn callback<T: InputTextCallbackHandler>(
 ) -> c_int {
     struct CallbackData<'a, T> {
         event_flag: InputTextFlags,
-        user_data: &'a mut UserData<T>,
+        user_data: &'a mut UserData<'a, T>,
     }
 
     let callback_data = unsafe {
         CallbackData {
             event_flag: InputTextFlags::from_bits((*data).EventFlag as u32).unwrap(),
-            user_data: &mut *((*data).UserData as *mut UserData<T>),
+            user_data: &mut *((*data).UserData as *mut UserData<'_, T>),
         }
     };
 
@@ -1244,7 +1757,7 @@ extern "C" fn callback<T: InputTextCallbackHandler>(
                 let buffer = &mut *callback_data.user_data.container;
 
                 // just confirm that we ARE working with our string.
-                debug_assert_eq!(buffer.as_ptr() as *const _, (*data).Buf);
+                debug_assert_eq!(buffer.as_mut_ptr() as *const _, (*data).Buf);
 
                 if requested_size > buffer.capacity() {
                     let additional_bytes = requested_size - buffer.len();
@@ -1298,3 +1811,166 @@ extern "C" fn callback<T: InputTextCallbackHandler>(
 /// actually run, since you will not have pass imgui any flags).
 pub struct PassthroughCallback;
 impl InputTextCallbackHandler for PassthroughCallback {}
+
+#[test]
+fn test_callback_edits() {
+    struct Complete<'a> {
+        positions: &'a mut Vec<usize>,
+    }
+
+    impl InputTextCallbackHandler for Complete<'_> {
+        fn on_completion(&mut self, mut data: TextCallbackData) {
+            self.positions.push(data.cursor_char_index());
+            data.set_cursor_char_index(1);
+            data.insert_at_cursor("ü");
+            self.positions.push(data.cursor_char_index());
+            data.move_cursor_chars(100);
+            data.insert_at_cursor("!");
+            data.set_selection_chars(2..3);
+            self.positions.push(data.selection().start);
+            data.insert_at_cursor("e");
+        }
+    }
+
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.io_mut().config_input_trickle_event_queue = false;
+    let mut text = crate::TextBuffer::new();
+    let mut positions = Vec::new();
+    for frame in 0..4 {
+        match frame {
+            2 => "héllo"
+                .chars()
+                .for_each(|c| ctx.io_mut().add_input_character(c)),
+            3 => ctx.io_mut().add_key_event(crate::Key::Tab, true),
+            _ => {}
+        }
+        let ui = ctx.new_frame();
+        ui.window("test").build(|| {
+            if frame == 0 {
+                ui.set_keyboard_focus_here();
+            }
+            ui.input_text("##text", &mut text)
+                .callback(
+                    InputTextCallback::COMPLETION,
+                    Complete {
+                        positions: &mut positions,
+                    },
+                )
+                .build();
+        });
+        ctx.render();
+    }
+    assert_eq!(text.to_str(), Ok("hüello!"));
+    assert_eq!(positions, [5, 2, 3]);
+}
+
+#[test]
+fn test_callback_invalid_utf8() {
+    struct Always<'a>(&'a mut Vec<(String, Vec<u8>)>);
+
+    impl InputTextCallbackHandler for Always<'_> {
+        fn on_always(&mut self, mut data: TextCallbackData) {
+            let text = data.str().to_owned();
+            let bytes = unsafe { data.str_as_bytes_mut() }.to_vec();
+            self.0.push((text, bytes));
+        }
+    }
+
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let mut bytes = b"ab\xffcd".to_vec();
+    let mut seen = Vec::new();
+    for frame in 0..3 {
+        let ui = ctx.new_frame();
+        ui.window("test").build(|| {
+            if frame == 0 {
+                ui.set_keyboard_focus_here();
+            }
+            ui.input_text("##bytes", &mut bytes)
+                .callback(InputTextCallback::ALWAYS, Always(&mut seen))
+                .build();
+        });
+        ctx.render();
+    }
+    // The text is cut at the invalid byte instead of panicking, and the bytes are left whole.
+    assert!(!seen.is_empty());
+    assert!(seen
+        .iter()
+        .all(|(text, bytes)| text == "ab" && bytes == b"ab\xffcd"));
+}
+
+#[test]
+fn test_validate_reverts() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.io_mut().config_input_trickle_event_queue = false;
+    let mut value = 3;
+    let mut errors = Vec::new();
+    for frame in 0..5 {
+        match frame {
+            2 => "-5"
+                .chars()
+                .for_each(|c| ctx.io_mut().add_input_character(c)),
+            3 => ctx.io_mut().add_key_event(crate::Key::Enter, true),
+            _ => {}
+        }
+        let ui = ctx.new_frame();
+        ui.window("test").build(|| {
+            if frame == 0 {
+                ui.set_keyboard_focus_here();
+            }
+            ui.input_scalar("##value", &mut value)
+                .validate(|value| match *value >= 0 {
+                    true => Ok(()),
+                    false => Err("negative".to_owned()),
+                })
+                .revert_invalid(true)
+                .build();
+            let id = ui.new_id_str("##value");
+            errors.push(ui.with_item_state(id, |state: &mut ValidationState| state.error.clone()));
+            if frame == 2 {
+                // Invalid values are kept while editing.
+                assert_eq!(value, -5);
+            }
+        });
+        ctx.render();
+    }
+    assert_eq!(value, 3);
+    assert_eq!(errors[2].as_deref(), Some("negative"));
+    assert_eq!(errors[4], None);
+}
+
+#[test]
+fn test_validate_waits_for_edit() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.io_mut().config_input_trickle_event_queue = false;
+    let mut value = -1;
+    let mut errors = Vec::new();
+    for frame in 0..5 {
+        match frame {
+            2 => "-5"
+                .chars()
+                .for_each(|c| ctx.io_mut().add_input_character(c)),
+            3 => ctx.io_mut().add_key_event(crate::Key::Enter, true),
+            _ => {}
+        }
+        let ui = ctx.new_frame();
+        ui.window("test").build(|| {
+            if frame == 0 {
+                ui.set_keyboard_focus_here();
+            }
+            ui.input_scalar("##value", &mut value)
+                .validate(|value| match *value >= 0 {
+                    true => Ok(()),
+                    false => Err("negative".to_owned()),
+                })
+                .validate_on(ValidationTrigger::DeactivationAfterEdit)
+                .build();
+            let id = ui.new_id_str("##value");
+            errors.push(ui.with_item_state(id, |state: &mut ValidationState| state.error.clone()));
+        });
+        ctx.render();
+    }
+    // The untouched initial value isn't flagged, even while the input is focused.
+    assert!(errors[..3].iter().all(Option::is_none), "{:?}", errors);
+    assert_eq!(value, -5);
+    assert_eq!(errors[4].as_deref(), Some("negative"));
+}
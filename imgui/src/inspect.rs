@@ -0,0 +1,409 @@
+use crate::internal::DataTypeKind;
+use crate::math::{MintVec2, MintVec3, MintVec4};
+use crate::{Drag, Ui};
+
+/// Options for [`Inspect::inspect_with`], usually set with `#[inspect(...)]` field attributes.
+///
+/// Implementations ignore the options which don't apply to them.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct InspectOptions<'a> {
+    /// Edits numbers with a slider in this range, or clamps drags to it if `speed` is set.
+    pub range: Option<(f64, f64)>,
+    /// Edits numbers with a drag of this speed.
+    pub speed: Option<f32>,
+    /// Display format of numbers, as a printf string.
+    pub format: Option<&'a str>,
+    /// Edits `[f32; 3]`, `[f32; 4]` and mint vectors with a color editor.
+    pub color: bool,
+}
+
+/// A value which can draw an editor for itself.
+///
+/// With the `derive` feature, this can be derived for structs, which edit each of their
+/// fields, and for enums with only unit variants, which pick a variant with a combo box.
+///
+/// ```no_run
+/// # #[cfg(feature = "derive")] {
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// #[derive(Inspect)]
+/// struct Light {
+///     #[inspect(color)]
+///     color: [f32; 3],
+///     #[inspect(min = 0.0, max = 10.0, tooltip = "In candela")]
+///     intensity: f32,
+///     #[inspect(read_only)]
+///     name: String,
+/// }
+///
+/// let mut light = Light {
+///     color: [1.0, 1.0, 1.0],
+///     intensity: 1.0,
+///     name: "Key light".to_owned(),
+/// };
+/// if light.inspect(ui, "light") {
+///     println!("light changed");
+/// }
+/// # }
+/// ```
+pub trait Inspect {
+    /// Draws an editor for the value, returning true if it was changed.
+    fn inspect(&mut self, ui: &Ui, label: &str) -> bool {
+        self.inspect_with(ui, label, &InspectOptions::default())
+    }
+
+    /// Draws an editor for the value with the given options, returning true if it was changed.
+    fn inspect_with(&mut self, ui: &Ui, label: &str, options: &InspectOptions<'_>) -> bool;
+}
+
+fn inspect_scalar<T: DataTypeKind>(
+    ui: &Ui,
+    label: &str,
+    value: &mut T,
+    options: &InspectOptions<'_>,
+    range: Option<(T, T)>,
+    drag_by_default: bool,
+) -> bool {
+    match (range, options.speed) {
+        (Some((min, max)), None) => {
+            let slider = ui.slider_config(label, min, max);
+            match options.format {
+                Some(format) => slider.display_format(format).build(value),
+                None => slider.build(value),
+            }
+        }
+        (range, speed) if speed.is_some() || drag_by_default => {
+            let mut drag = Drag::new(label).speed(speed.unwrap_or(1.0));
+            if let Some((min, max)) = range {
+                drag = drag.range(min, max);
+            }
+            match options.format {
+                Some(format) => drag.display_format(format).build(ui, value),
+                None => drag.build(ui, value),
+            }
+        }
+        _ => {
+            let input = ui.input_scalar(label, value);
+            match options.format {
+                Some(format) => input.display_format(format).build(),
+                None => input.build(),
+            }
+        }
+    }
+}
+
+macro_rules! impl_inspect_for_scalar {
+    ($drag_by_default:expr; $($ty:ty),*) => {
+        $(
+            impl Inspect for $ty {
+                fn inspect_with(
+                    &mut self,
+                    ui: &Ui,
+                    label: &str,
+                    options: &InspectOptions<'_>,
+                ) -> bool {
+                    let range = options.range.map(|(min, max)| (min as $ty, max as $ty));
+                    inspect_scalar(ui, label, self, options, range, $drag_by_default)
+                }
+            }
+        )*
+    };
+}
+
+impl_inspect_for_scalar!(false; i8, u8, i16, u16, i32, u32, i64, u64, isize, usize);
+impl_inspect_for_scalar!(true; f32, f64);
+
+impl Inspect for bool {
+    fn inspect_with(&mut self, ui: &Ui, label: &str, _: &InspectOptions<'_>) -> bool {
+        ui.checkbox(label, self)
+    }
+}
+
+impl Inspect for String {
+    fn inspect_with(&mut self, ui: &Ui, label: &str, _: &InspectOptions<'_>) -> bool {
+        ui.input_text(label, self).build()
+    }
+}
+
+fn inspect_floats(ui: &Ui, label: &str, values: &mut [f32], options: &InspectOptions<'_>) -> bool {
+    let range = options.range.map(|(min, max)| (min as f32, max as f32));
+    match (range, options.speed) {
+        (Some((min, max)), None) => {
+            let slider = ui.slider_config(label, min, max);
+            match options.format {
+                Some(format) => slider.display_format(format).build_array(values),
+                None => slider.build_array(values),
+            }
+        }
+        (range, speed) => {
+            let mut drag = Drag::new(label).speed(speed.unwrap_or(1.0));
+            if let Some((min, max)) = range {
+                drag = drag.range(min, max);
+            }
+            match options.format {
+                Some(format) => drag.display_format(format).build_array(ui, values),
+                None => drag.build_array(ui, values),
+            }
+        }
+    }
+}
+
+impl Inspect for [f32; 2] {
+    fn inspect_with(&mut self, ui: &Ui, label: &str, options: &InspectOptions<'_>) -> bool {
+        inspect_floats(ui, label, self, options)
+    }
+}
+
+impl Inspect for [f32; 3] {
+    fn inspect_with(&mut self, ui: &Ui, label: &str, options: &InspectOptions<'_>) -> bool {
+        if options.color {
+            ui.color_edit3(label, self)
+        } else {
+            inspect_floats(ui, label, self, options)
+        }
+    }
+}
+
+impl Inspect for [f32; 4] {
+    fn inspect_with(&mut self, ui: &Ui, label: &str, options: &InspectOptions<'_>) -> bool {
+        if options.color {
+            ui.color_edit4(label, self)
+        } else {
+            inspect_floats(ui, label, self, options)
+        }
+    }
+}
+
+macro_rules! impl_inspect_for_mint {
+    ($($ty:ty => $array:ty),*) => {
+        $(
+            impl Inspect for $ty {
+                fn inspect_with(
+                    &mut self,
+                    ui: &Ui,
+                    label: &str,
+                    options: &InspectOptions<'_>,
+                ) -> bool {
+                    let mut array: $array = (*self).into();
+                    let changed = array.inspect_with(ui, label, options);
+                    if changed {
+                        *self = array.into();
+                    }
+                    changed
+                }
+            }
+        )*
+    };
+}
+
+impl_inspect_for_mint!(MintVec2 => [f32; 2], MintVec3 => [f32; 3], MintVec4 => [f32; 4]);
+
+/// Shows a checkbox toggling between `None` and `Some(T::default())`, followed by the editor
+/// of the value if there is one.
+///
+/// As `Option` has an inherent `inspect` method, call this one as
+/// `Inspect::inspect(&mut value, ui, label)`.
+impl<T: Inspect + Default> Inspect for Option<T> {
+    fn inspect_with(&mut self, ui: &Ui, label: &str, options: &InspectOptions<'_>) -> bool {
+        let _id = ui.push_id(label);
+        let mut enabled = self.is_some();
+        let changed = ui.checkbox("##enabled", &mut enabled);
+        if changed {
+            *self = enabled.then(T::default);
+        }
+        ui.same_line();
+        match self {
+            Some(value) => value.inspect_with(ui, label, options) || changed,
+            None => {
+                ui.text_disabled(label);
+                changed
+            }
+        }
+    }
+}
+
+/// Shows the elements inside a tree node, with the options applied to every element.
+impl<T: Inspect> Inspect for Vec<T> {
+    fn inspect_with(&mut self, ui: &Ui, label: &str, options: &InspectOptions<'_>) -> bool {
+        let mut changed = false;
+        if let Some(_node) = ui.tree_node(label) {
+            for (index, value) in self.iter_mut().enumerate() {
+                let _id = ui.push_id_usize(index);
+                changed |= value.inspect_with(ui, &format!("[{}]", index), options);
+            }
+        }
+        changed
+    }
+}
+
+/// Builds editors in a window for five frames, pressing the mouse `x` pixels right of the start
+/// of the first editor and dragging it `dx` pixels further. Returns true if any frame reported a
+/// change.
+#[cfg(test)]
+fn click_and_drag(
+    ctx: &mut crate::Context,
+    x: f32,
+    dx: f32,
+    mut build: impl FnMut(&Ui) -> bool,
+) -> bool {
+    ctx.io_mut().config_input_trickle_event_queue = false;
+    let mut start = [0.0; 2];
+    let mut changed = false;
+    for frame in 0..5 {
+        let io = ctx.io_mut();
+        match frame {
+            1 => io.add_mouse_pos_event([start[0] + x, start[1]]),
+            2 => io.add_mouse_button_event(crate::MouseButton::Left, true),
+            3 => io.add_mouse_pos_event([start[0] + x + dx, start[1]]),
+            4 => io.add_mouse_button_event(crate::MouseButton::Left, false),
+            _ => {}
+        }
+        let ui = ctx.new_frame();
+        ui.window("inspect")
+            .position([0.0, 0.0], crate::Condition::Always)
+            .size([400.0, 300.0], crate::Condition::Always)
+            .build(|| {
+                let [x, y] = ui.cursor_screen_pos();
+                start = [x, y + ui.frame_height() / 2.0];
+                changed |= build(ui);
+            });
+        ctx.render();
+    }
+    changed
+}
+
+#[test]
+fn test_inspect_std_types() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.frame();
+    let mut speed = 1.5f32;
+    let mut count = 3u8;
+    let mut enabled = Some(true);
+    let mut color = MintVec3::from([0.5, 0.5, 0.5]);
+    let mut names = vec![String::from("a"), String::from("b")];
+    let options = InspectOptions {
+        range: Some((0.0, 10.0)),
+        format: Some("%.1f"),
+        ..Default::default()
+    };
+    ui.window("inspect").build(|| {
+        assert!(!speed.inspect_with(ui, "speed", &options));
+        assert!(!count.inspect(ui, "count"));
+        assert!(!Inspect::inspect(&mut enabled, ui, "enabled"));
+        let color_options = InspectOptions {
+            color: true,
+            ..Default::default()
+        };
+        assert!(!color.inspect_with(ui, "color", &color_options));
+        let text = ui.log_to_buffer(|| {
+            names.inspect(ui, "names");
+        });
+        assert!(text.unwrap().contains("{ b } [1]"));
+    });
+    ctx.render();
+
+    // A range edits with a slider, which jumps to the clicked value.
+    assert!(click_and_drag(&mut ctx, 1.0, 0.0, |ui| speed
+        .inspect_with(ui, "speed", &options)));
+    assert_eq!(speed, 0.0);
+    // Without options, floats are dragged and integers are typed in.
+    assert!(click_and_drag(&mut ctx, 10.0, 20.0, |ui| speed.inspect(ui, "speed")));
+    assert_eq!(speed, 20.0);
+    assert!(!click_and_drag(&mut ctx, 10.0, 20.0, |ui| count.inspect(ui, "count")));
+    assert_eq!(count, 3);
+    // The checkbox of an option clears it.
+    assert!(click_and_drag(&mut ctx, 5.0, 0.0, |ui| Inspect::inspect(
+        &mut enabled,
+        ui,
+        "enabled"
+    )));
+    assert_eq!(enabled, None);
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_derive_inspect() {
+    #[derive(Clone, Copy, Debug, PartialEq, crate::Inspect)]
+    enum Mode {
+        Fast,
+        #[inspect(label = "Very slow")]
+        Slow,
+    }
+
+    #[derive(crate::Inspect)]
+    struct Settings {
+        #[inspect(min = 0, max = 100, tooltip = "Percent")]
+        volume: i32,
+        #[inspect(speed = 0.1, format = "%.2f")]
+        scale: f32,
+        #[inspect(color)]
+        tint: [f32; 4],
+        #[inspect(read_only, label = "Name")]
+        name: String,
+        mode: Mode,
+        #[inspect(nested)]
+        offset: Option<Offset>,
+        #[inspect(skip)]
+        _cache: Vec<u8>,
+    }
+
+    #[derive(Default, crate::Inspect)]
+    struct Offset(f32, f32);
+
+    #[derive(crate::Inspect)]
+    struct Wrapper<T> {
+        #[inspect(speed = 2)]
+        value: T,
+    }
+
+    #[derive(crate::Inspect)]
+    struct Locked {
+        #[inspect(read_only, speed = 2)]
+        value: i32,
+    }
+
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.frame();
+    let mut settings = Settings {
+        volume: 50,
+        scale: 1.0,
+        tint: [1.0; 4],
+        name: "default".to_owned(),
+        mode: Mode::Slow,
+        offset: Some(Offset(0.0, 0.0)),
+        _cache: Vec::new(),
+    };
+    ui.window("settings").build(|| {
+        assert!(!settings.inspect(ui, "settings"));
+        let mut log = |depth| {
+            ui.log_to_buffer_with_depth(depth, || {
+                settings.inspect(ui, "settings");
+            })
+            .unwrap()
+        };
+        // Nested fields are only shown once their tree node is opened.
+        let closed = log(0);
+        let opened = log(-1);
+        assert!(closed.contains("{ default } Name\n"));
+        assert!(closed.contains("{ Very slow } mode\n"));
+        assert!(closed.contains("> offset\n"));
+        assert!(!closed.contains("{ 0.000 } 0"));
+        assert!(opened.contains("{ 0.000 } 0\n"));
+        assert!(opened.contains("{ 0.000 } 1\n"));
+        assert!(!opened.contains("_cache"));
+    });
+    ctx.render();
+
+    // `min` and `max` edit with a slider, which clamps the value.
+    assert!(click_and_drag(&mut ctx, 1.0, 1000.0, |ui| settings.inspect(ui, "settings")));
+    assert_eq!(settings.volume, 100);
+    // `speed` edits with a drag, here of an integer which is otherwise typed in.
+    let mut wrapper = Wrapper { value: 1 };
+    assert!(click_and_drag(&mut ctx, 10.0, 20.0, |ui| wrapper.inspect(ui, "wrapper")));
+    assert_eq!(wrapper.value, 41);
+    // `read_only` disables the field.
+    let mut locked = Locked { value: 1 };
+    assert!(!click_and_drag(&mut ctx, 10.0, 20.0, |ui| locked.inspect(ui, "locked")));
+    assert_eq!(locked.value, 1);
+}
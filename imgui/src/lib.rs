@@ -87,33 +87,46 @@
 
 pub extern crate imgui_sys as sys;
 
+// lets the derive macros, which refer to `::imgui`, be used in this crate's tests
+#[cfg(all(test, feature = "derive"))]
+extern crate self as imgui;
+
 use std::cell;
 use std::os::raw::c_char;
 
 pub use self::clipboard::*;
 pub use self::color::ImColor32;
+pub use self::command_palette::*;
 pub use self::context::*;
 #[cfg(feature = "docking")]
 pub use self::docking_utils::*;
+pub use self::document_set::*;
 pub use self::drag_drop::{DragDropFlags, DragDropSource, DragDropTarget};
 pub use self::draw_list::{ChannelsSplit, DrawListMut};
 pub use self::fonts::atlas::*;
 pub use self::fonts::font::*;
 pub use self::fonts::glyph::*;
 pub use self::fonts::glyph_ranges::*;
+pub use self::form::*;
 pub use self::input::keyboard::*;
 pub use self::input::mouse::*;
 pub use self::input_widget::*;
+pub use self::inspect::*;
 pub use self::io::*;
 pub use self::layout::*;
 pub use self::list_clipper::ListClipper;
+pub use self::logging::*;
 pub use self::platform_io::*;
 pub use self::plothistogram::PlotHistogram;
 pub use self::plotlines::PlotLines;
 pub use self::popups::*;
 pub use self::render::draw_data::*;
+pub use self::render::flatten::*;
 pub use self::render::renderer::*;
+pub use self::render::snapshot::*;
+pub use self::stack_layout::*;
 pub use self::stacks::*;
+pub use self::storage::*;
 pub use self::string::*;
 pub use self::style::*;
 
@@ -125,16 +138,22 @@ pub use self::widget::color_editors::*;
 pub use self::widget::combo_box::*;
 pub use self::widget::drag::*;
 pub use self::widget::image::*;
+pub use self::widget::knob::*;
 pub use self::widget::list_box::*;
 pub use self::widget::menu::*;
 pub use self::widget::misc::*;
 pub use self::widget::progress_bar::*;
+pub use self::widget::scalar::{Primitive, Scalar};
 pub use self::widget::selectable::*;
 pub use self::widget::slider::*;
+pub use self::widget::spinner::*;
 pub use self::widget::tab::*;
 pub use self::widget::tree::*;
 pub use self::window::child_window::*;
+pub use self::window::splitter::*;
 pub use self::window::*;
+#[cfg(feature = "derive")]
+pub use imgui_derive::Inspect;
 use internal::RawCast;
 use math::*;
 
@@ -147,32 +166,40 @@ mod tokens;
 mod clipboard;
 pub mod color;
 mod columns;
+mod command_palette;
 mod context;
 #[cfg(feature = "docking")]
 mod dock_space;
 #[cfg(feature = "docking")]
 mod docking_utils;
+mod document_set;
 
 pub mod drag_drop;
 pub mod draw_list;
 mod fonts;
+mod form;
 mod input;
 mod input_widget;
+mod inspect;
 pub mod internal;
 mod io;
 mod layout;
 mod list_clipper;
+mod logging;
 mod math;
 mod platform_io;
 mod plothistogram;
 mod plotlines;
 mod popups;
+pub mod remote;
 mod render;
 #[cfg(feature = "docking")]
 pub mod viewport_issue_fix;
 #[cfg(feature = "docking")]
 pub mod monitor_init_fix;
+mod stack_layout;
 mod stacks;
+mod storage;
 mod style;
 #[cfg(feature = "tables-api")]
 mod tables;
@@ -507,12 +534,13 @@ impl Ui {
 impl<'ui> Ui {
     /// # Widgets: Input
     ///
-    /// Edits text in a single line input widget
+    /// Edits text in a single line input widget. Besides `String`, any
+    /// [`TextBufferLike`] buffer can be edited.
     #[doc(alias = "InputText", alias = "InputTextWithHint")]
     pub fn input_text<'p, L: AsRef<str>>(
         &'ui self,
         label: L,
-        buf: &'p mut String,
+        buf: &'p mut impl TextBufferLike,
     ) -> InputText<'ui, 'p, L> {
         InputText::new(self, label, buf)
     }
@@ -524,7 +552,7 @@ impl<'ui> Ui {
     pub fn input_text_multiline<'p, L: AsRef<str>>(
         &'ui self,
         label: L,
-        buf: &'p mut String,
+        buf: &'p mut impl TextBufferLike,
         size: [f32; 2],
     ) -> InputTextMultiline<'ui, 'p, L> {
         InputTextMultiline::new(self, label, buf, size)
@@ -638,7 +666,7 @@ impl<'ui> Ui {
     ) -> InputScalar<'ui, 'p, T, L>
     where
         L: AsRef<str>,
-        T: internal::DataTypeKind,
+        T: Scalar,
     {
         InputScalar::new(self, label, value)
     }
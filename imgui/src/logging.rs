@@ -0,0 +1,423 @@
+use std::ffi::CStr;
+use std::fmt;
+use std::os::raw::{c_char, c_void};
+use std::panic::catch_unwind;
+use std::path::Path;
+use std::process;
+use std::ptr;
+
+use crate::sys;
+use crate::{Id, Ui};
+
+/// Destination of the text captured by [`Ui::log_to`].
+///
+/// To capture the text in a `String`, use [`Ui::log_to_buffer`].
+#[derive(Copy, Clone, Debug)]
+pub enum LogTarget<'a> {
+    /// Writes to standard output.
+    Tty,
+    /// Appends to a file. `None` uses the log filename of the context (see
+    /// [`Context::set_log_filename`](crate::Context::set_log_filename)).
+    File(Option<&'a Path>),
+    /// Copies the text to the clipboard when the log is finished.
+    Clipboard,
+}
+
+/// The error returned when a log can't be started.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LogError {
+    /// Another log is active. Dear ImGui ignores a second log instead of nesting it.
+    AlreadyActive,
+    /// The path of a [`LogTarget::File`] is not valid UTF-8.
+    NonUtf8Path,
+}
+
+impl fmt::Display for LogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogError::AlreadyActive => f.pad("A log is already active"),
+            LogError::NonUtf8Path => f.pad("Log file path is not valid UTF-8"),
+        }
+    }
+}
+
+impl std::error::Error for LogError {}
+
+/// The log started from Rust in the current frame.
+///
+/// Dear ImGui finishes any log when a top-level window ends, so a log never outlives the frame it
+/// was started in.
+#[derive(Default)]
+struct LogState {
+    frame: i32,
+    active: bool,
+    /// Increased for each log, so a token doesn't finish a log started after its own ended.
+    generation: u64,
+}
+
+impl Ui {
+    fn with_log_state<R>(&self, f: impl FnOnce(&mut LogState) -> R) -> R {
+        // The state is private to this module, so its type alone tells it apart from other states.
+        let frame = self.frame_count();
+        self.with_item_state(Id(0), |state: &mut LogState| {
+            if state.frame != frame {
+                *state = LogState {
+                    frame,
+                    active: false,
+                    generation: state.generation,
+                };
+            }
+            f(state)
+        })
+    }
+
+    /// Records the log just started by `start`, failing if a token of another log is alive.
+    fn begin_log(&self, start: impl FnOnce()) -> Result<LogToken<'_>, LogError> {
+        if self.with_log_state(|state| state.active) {
+            return Err(LogError::AlreadyActive);
+        }
+        start();
+        let generation = self.with_log_state(|state| {
+            state.active = true;
+            state.generation += 1;
+            state.generation
+        });
+        Ok(LogToken {
+            ui: self,
+            generation,
+        })
+    }
+}
+
+/// Tracks a log started with [`Ui::log_to`] that can be finished by calling `.end()` or by
+/// dropping.
+#[must_use]
+pub struct LogToken<'a> {
+    ui: &'a Ui,
+    generation: u64,
+}
+
+impl LogToken<'_> {
+    /// Finishes the log and flushes the captured text to its target.
+    #[doc(alias = "LogFinish")]
+    #[inline]
+    pub fn end(self) {
+        // left empty for drop
+    }
+}
+
+impl Drop for LogToken<'_> {
+    fn drop(&mut self) {
+        let generation = self.generation;
+        let owned = self.ui.with_log_state(|state| {
+            let owned = state.active && state.generation == generation;
+            if owned {
+                state.active = false;
+            }
+            owned
+        });
+        if owned {
+            unsafe { sys::igLogFinish() };
+        }
+    }
+}
+
+/// The text of a buffer log, and the clipboard setter it temporarily replaces.
+struct LogCapture {
+    text: String,
+    captured: bool,
+    set_fn: Option<unsafe extern "C" fn(*mut sys::ImGuiContext, *const c_char)>,
+    user_data: *mut c_void,
+}
+
+/// Points the clipboard setter at a [`LogCapture`], restoring the previous setter when dropped.
+///
+/// This is only ever a local of [`Ui::log_to_buffer_with_depth`], so the setter is restored
+/// before the capture goes out of scope, even when unwinding.
+struct CaptureHook(*mut LogCapture);
+
+impl CaptureHook {
+    unsafe fn install(capture: *mut LogCapture) -> CaptureHook {
+        let platform_io = sys::igGetPlatformIO();
+        (*capture).set_fn = (*platform_io).Platform_SetClipboardTextFn;
+        (*capture).user_data = (*platform_io).Platform_ClipboardUserData;
+        (*platform_io).Platform_SetClipboardTextFn = Some(capture_log_text);
+        (*platform_io).Platform_ClipboardUserData = capture as *mut c_void;
+        CaptureHook(capture)
+    }
+}
+
+impl Drop for CaptureHook {
+    fn drop(&mut self) {
+        unsafe {
+            let platform_io = sys::igGetPlatformIO();
+            (*platform_io).Platform_SetClipboardTextFn = (*self.0).set_fn;
+            (*platform_io).Platform_ClipboardUserData = (*self.0).user_data;
+        }
+    }
+}
+
+unsafe extern "C" fn capture_log_text(ctx: *mut sys::ImGuiContext, text: *const c_char) {
+    let result = catch_unwind(|| {
+        let platform_io = sys::igGetPlatformIO();
+        let capture = &mut *((*platform_io).Platform_ClipboardUserData as *mut LogCapture);
+        if !capture.captured {
+            capture.captured = true;
+            capture
+                .text
+                .push_str(&CStr::from_ptr(text).to_string_lossy());
+        } else if let Some(set_fn) = capture.set_fn {
+            // Later clipboard writes, e.g. by another log, go where they would have gone.
+            (*platform_io).Platform_ClipboardUserData = capture.user_data;
+            set_fn(ctx, text);
+            (*platform_io).Platform_ClipboardUserData = capture as *mut LogCapture as *mut _;
+        }
+    });
+    result.unwrap_or_else(|_| process::abort());
+}
+
+/// # Logging
+impl Ui {
+    /// Starts capturing the text of the following widgets into `target`.
+    ///
+    /// Tree nodes and collapsing headers are logged as if opened, up to the default depth of 2.
+    /// The log is finished when the returned token is dropped, or at the latest when the current
+    /// top-level window ends.
+    ///
+    /// Fails while the token of another log started with this function or
+    /// [`log_to_buffer`](Self::log_to_buffer) is alive. Logs started by
+    /// [`log_buttons`](Self::log_buttons), by Dear ImGui itself or from C++ can't be detected: in
+    /// that case the returned token doesn't capture anything.
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// # let mut ctx = Context::create();
+    /// # let ui = ctx.frame();
+    /// if ui.button("Copy as text") {
+    ///     if let Ok(_log) = ui.log_to(LogTarget::Clipboard) {
+    ///         ui.text("Only logged text is copied");
+    ///     }
+    /// }
+    /// ```
+    #[doc(alias = "LogToTTY", alias = "LogToFile", alias = "LogToClipboard")]
+    pub fn log_to<'a>(&'a self, target: LogTarget<'a>) -> Result<LogToken<'a>, LogError> {
+        self.log_to_with_depth(target, -1)
+    }
+
+    /// Starts capturing text like [`log_to`](Self::log_to), opening tree nodes up to
+    /// `auto_open_depth` levels deep.
+    pub fn log_to_with_depth<'a>(
+        &'a self,
+        target: LogTarget<'a>,
+        auto_open_depth: i32,
+    ) -> Result<LogToken<'a>, LogError> {
+        let filename = match target {
+            LogTarget::File(Some(path)) => {
+                self.scratch_txt(path.to_str().ok_or(LogError::NonUtf8Path)?)
+            }
+            _ => ptr::null(),
+        };
+        self.begin_log(|| unsafe {
+            match target {
+                LogTarget::Tty => sys::igLogToTTY(auto_open_depth),
+                LogTarget::File(_) => sys::igLogToFile(auto_open_depth, filename),
+                LogTarget::Clipboard => sys::igLogToClipboard(auto_open_depth),
+            }
+        })
+    }
+
+    /// Captures the text of the widgets built by `f` into a string.
+    ///
+    /// Tree nodes and collapsing headers are logged as if opened, up to the default depth of 2.
+    /// The text is gathered like a clipboard log, without touching the clipboard. As with
+    /// [`log_to`](Self::log_to), the end of a top-level window inside `f` finishes the log early.
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// # let mut ctx = Context::create();
+    /// # let ui = ctx.frame();
+    /// let text = ui.log_to_buffer(|| ui.text("Captured")).unwrap();
+    /// assert!(text.contains("Captured"));
+    /// ```
+    #[doc(alias = "LogToBuffer")]
+    pub fn log_to_buffer(&self, f: impl FnOnce()) -> Result<String, LogError> {
+        self.log_to_buffer_with_depth(-1, f)
+    }
+
+    /// Captures text like [`log_to_buffer`](Self::log_to_buffer), opening tree nodes up to
+    /// `auto_open_depth` levels deep.
+    pub fn log_to_buffer_with_depth(
+        &self,
+        auto_open_depth: i32,
+        f: impl FnOnce(),
+    ) -> Result<String, LogError> {
+        let mut capture = LogCapture {
+            text: String::new(),
+            captured: false,
+            set_fn: None,
+            user_data: ptr::null_mut(),
+        };
+        {
+            // The hook is declared first so it's restored after the log is finished.
+            let _hook = unsafe { CaptureHook::install(&mut capture) };
+            let _log = self.begin_log(|| unsafe { sys::igLogToClipboard(auto_open_depth) })?;
+            f();
+        }
+        Ok(capture.text)
+    }
+
+    /// Adds text to the active log without displaying it.
+    ///
+    /// Does nothing if no log is active.
+    #[doc(alias = "LogText")]
+    pub fn log_text(&self, text: impl AsRef<str>) {
+        unsafe { sys::igLogText(c"%s".as_ptr(), self.scratch_txt(text)) }
+    }
+
+    /// Renders the "Log To TTY", "Log To File" and "Log To Clipboard" buttons, which log the rest
+    /// of the current window when clicked.
+    ///
+    /// Unlike Dear ImGui's `LogButtons`, there's no slider for the default depth, so the logs open
+    /// tree nodes up to the default depth of 2. Nothing is logged while the token of a log started
+    /// with [`log_to`](Self::log_to) is alive.
+    #[doc(alias = "LogButtons")]
+    pub fn log_buttons(&self) {
+        let id = self.push_id("LogButtons");
+        let tty = self.button("Log To TTY");
+        self.same_line();
+        let file = self.button("Log To File");
+        self.same_line();
+        let clipboard = self.button("Log To Clipboard");
+        id.end();
+
+        // Start logging after the buttons so they don't appear in the log. The log has no token,
+        // and Dear ImGui finishes it without telling, so it isn't recorded as active.
+        if !(tty || file || clipboard) || self.with_log_state(|state| state.active) {
+            return;
+        }
+        unsafe {
+            if tty {
+                sys::igLogToTTY(-1);
+            } else if file {
+                sys::igLogToFile(-1, ptr::null());
+            } else {
+                sys::igLogToClipboard(-1);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_log_to_buffer() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.frame();
+    let text = ui
+        .log_to_buffer(|| {
+            assert_eq!(
+                ui.log_to(LogTarget::Clipboard).err(),
+                Some(LogError::AlreadyActive)
+            );
+            assert_eq!(ui.log_to_buffer(|| {}), Err(LogError::AlreadyActive));
+            ui.text("Hello");
+            ui.log_text("world");
+        })
+        .unwrap();
+    assert!(text.contains("Hello"));
+    assert!(text.contains("world"));
+    assert!(ui.log_to(LogTarget::Clipboard).is_ok());
+}
+
+#[test]
+fn test_log_to_buffer_across_window() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct Clipboard(Rc<RefCell<Vec<String>>>);
+    impl crate::ClipboardBackend for Clipboard {
+        fn get(&mut self) -> Option<String> {
+            None
+        }
+        fn set(&mut self, value: &str) {
+            self.0.borrow_mut().push(value.to_owned());
+        }
+    }
+
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let clipboard = Rc::new(RefCell::new(Vec::new()));
+    ctx.set_clipboard_backend(Clipboard(clipboard.clone()));
+    let ui = ctx.frame();
+    // The end of the window finishes the log before the closure returns.
+    let text = ui
+        .log_to_buffer(|| {
+            ui.window("logged").build(|| ui.text("Inside"));
+            ui.text("After");
+            ui.set_clipboard_text("Copied");
+        })
+        .unwrap();
+    assert!(text.contains("Inside"));
+    assert!(!text.contains("After"));
+    assert_eq!(*clipboard.borrow(), ["Copied"]);
+    ui.set_clipboard_text("Restored");
+    assert_eq!(*clipboard.borrow(), ["Copied", "Restored"]);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        let path = Path::new(std::ffi::OsStr::from_bytes(b"\xff.log"));
+        assert_eq!(
+            ui.log_to(LogTarget::File(Some(path))).err(),
+            Some(LogError::NonUtf8Path)
+        );
+    }
+}
+
+#[test]
+fn test_log_buttons_finished_by_window() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct Clipboard(Rc<RefCell<Vec<String>>>);
+    impl crate::ClipboardBackend for Clipboard {
+        fn get(&mut self) -> Option<String> {
+            None
+        }
+        fn set(&mut self, value: &str) {
+            self.0.borrow_mut().push(value.to_owned());
+        }
+    }
+
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.io_mut().config_input_trickle_event_queue = false;
+    let clipboard = Rc::new(RefCell::new(Vec::new()));
+    ctx.set_clipboard_backend(Clipboard(clipboard.clone()));
+    let mut button = [0.0; 2];
+    for frame in 0..4 {
+        match frame {
+            1 => ctx.io_mut().add_mouse_pos_event(button),
+            2 => ctx
+                .io_mut()
+                .add_mouse_button_event(crate::MouseButton::Left, true),
+            3 => ctx
+                .io_mut()
+                .add_mouse_button_event(crate::MouseButton::Left, false),
+            _ => {}
+        }
+        let ui = ctx.new_frame();
+        ui.window("buttons")
+            .position([0.0, 0.0], crate::Condition::Always)
+            .build(|| {
+                ui.log_buttons();
+                // The center of the "Log To Clipboard" button.
+                let ([x, y], [w, h]) = (ui.item_rect_min(), ui.item_rect_size());
+                button = [x + w / 2.0, y + h / 2.0];
+                ui.text("Logged");
+            });
+        // The end of the window finished the log of the button, so a new log can start.
+        let text = ui.log_to_buffer(|| ui.text("Next")).unwrap();
+        assert!(text.contains("Next"));
+        ctx.render();
+    }
+    let copied = clipboard.borrow();
+    assert_eq!(copied.len(), 1);
+    assert!(copied[0].contains("Logged"));
+}
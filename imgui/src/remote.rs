@@ -0,0 +1,764 @@
+//! Streaming of frames to a viewer running in another process.
+//!
+//! A headless process (for example a server without a window) creates a [`RemoteServer`] and
+//! sends it every rendered frame. A viewer process connects with a [`RemoteViewer`], receives the
+//! frames as [`FrameSnapshot`]s that can be passed to any renderer, and sends mouse and keyboard
+//! input back, which the server feeds into its [`Io`].
+//!
+//! Frames after the first one only contain the vertices which changed since the previous frame,
+//! and the font atlas is only uploaded once per connection.
+//!
+//! ```no_run
+//! # use imgui::*;
+//! # use imgui::remote::*;
+//! # let mut ctx = Context::create();
+//! let listener = std::net::TcpListener::bind("127.0.0.1:7878").unwrap();
+//! let mut server = RemoteServer::accept(&listener).unwrap();
+//! server.send_font_atlas(ctx.fonts()).unwrap();
+//! loop {
+//!     server.poll_input(ctx.io_mut()).unwrap();
+//!     let ui = ctx.new_frame();
+//!     ui.text("Hello from a headless process");
+//!     server.send_frame(ctx.render()).unwrap();
+//! }
+//! ```
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+
+use crate::fonts::atlas::FontAtlas;
+use crate::input::keyboard::Key;
+use crate::input::mouse::MouseButton;
+use crate::io::Io;
+use crate::render::draw_data::{DrawData, DrawVert};
+use crate::render::renderer::TextureId;
+use crate::render::snapshot::{
+    put_len, put_vertex, FrameSnapshot, Reader, SnapshotDecodeError, VERTEX_SIZE,
+};
+
+const MSG_FONT_ATLAS: u8 = 0;
+const MSG_FRAME: u8 = 1;
+const MSG_FRAME_DELTA: u8 = 2;
+const MSG_INPUT: u8 = 3;
+
+/// Largest message accepted from the other side, which is enough for a 4096x4096 font atlas.
+const MAX_MESSAGE_LEN: usize = 80 << 20;
+
+const INPUT_MOUSE_POS: u8 = 0;
+const INPUT_MOUSE_BUTTON: u8 = 1;
+const INPUT_MOUSE_WHEEL: u8 = 2;
+const INPUT_KEY: u8 = 3;
+const INPUT_CHAR: u8 = 4;
+const INPUT_FOCUS: u8 = 5;
+
+/// A connected socket that frames can be streamed over.
+pub trait RemoteStream: Read + Write {
+    /// Moves the socket into or out of nonblocking mode.
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()>;
+}
+
+impl RemoteStream for TcpStream {
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        TcpStream::set_nonblocking(self, nonblocking)
+    }
+}
+
+#[cfg(unix)]
+impl RemoteStream for UnixStream {
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        UnixStream::set_nonblocking(self, nonblocking)
+    }
+}
+
+/// An input event sent by a [`RemoteViewer`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum RemoteInput {
+    MousePos([f32; 2]),
+    MouseButton(MouseButton, bool),
+    MouseWheel([f32; 2]),
+    Key(Key, bool),
+    Char(char),
+    Focus(bool),
+}
+
+impl RemoteInput {
+    /// Queues this event in `io`, as a platform backend would.
+    pub fn apply(self, io: &mut Io) {
+        match self {
+            RemoteInput::MousePos(pos) => io.add_mouse_pos_event(pos),
+            RemoteInput::MouseButton(button, down) => io.add_mouse_button_event(button, down),
+            RemoteInput::MouseWheel(wheel) => io.add_mouse_wheel_event(wheel),
+            RemoteInput::Key(key, down) => io.add_key_event(key, down),
+            RemoteInput::Char(c) => io.add_input_character(c),
+            RemoteInput::Focus(focused) => io.add_focus_event(focused),
+        }
+    }
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        match *self {
+            RemoteInput::MousePos(pos) => {
+                out.push(INPUT_MOUSE_POS);
+                put_f32s(out, &pos);
+            }
+            RemoteInput::MouseButton(button, down) => {
+                out.push(INPUT_MOUSE_BUTTON);
+                out.push(button as u8);
+                out.push(down as u8);
+            }
+            RemoteInput::MouseWheel(wheel) => {
+                out.push(INPUT_MOUSE_WHEEL);
+                put_f32s(out, &wheel);
+            }
+            RemoteInput::Key(key, down) => {
+                out.push(INPUT_KEY);
+                out.extend_from_slice(&(key as u32).to_le_bytes());
+                out.push(down as u8);
+            }
+            RemoteInput::Char(c) => {
+                out.push(INPUT_CHAR);
+                out.extend_from_slice(&(c as u32).to_le_bytes());
+            }
+            RemoteInput::Focus(focused) => {
+                out.push(INPUT_FOCUS);
+                out.push(focused as u8);
+            }
+        }
+    }
+
+    fn decode(r: &mut Reader<'_>) -> Result<RemoteInput, RemoteError> {
+        let tag = r.u8()?;
+        let event = match tag {
+            INPUT_MOUSE_POS => RemoteInput::MousePos([r.f32()?, r.f32()?]),
+            INPUT_MOUSE_BUTTON => {
+                let button = r.u8()?;
+                let button = *MouseButton::VARIANTS
+                    .get(button as usize)
+                    .ok_or(RemoteError::InvalidInput(tag))?;
+                RemoteInput::MouseButton(button, r.u8()? != 0)
+            }
+            INPUT_MOUSE_WHEEL => RemoteInput::MouseWheel([r.f32()?, r.f32()?]),
+            INPUT_KEY => {
+                let key = u32::from_le_bytes(r.array()?);
+                let key = *Key::VARIANTS
+                    .iter()
+                    .find(|&&k| k as u32 == key)
+                    .ok_or(RemoteError::InvalidInput(tag))?;
+                RemoteInput::Key(key, r.u8()? != 0)
+            }
+            INPUT_CHAR => RemoteInput::Char(
+                char::from_u32(u32::from_le_bytes(r.array()?))
+                    .ok_or(RemoteError::InvalidInput(tag))?,
+            ),
+            INPUT_FOCUS => RemoteInput::Focus(r.u8()? != 0),
+            _ => return Err(RemoteError::InvalidInput(tag)),
+        };
+        Ok(event)
+    }
+}
+
+/// Streams frames to a [`RemoteViewer`] and receives its input.
+pub struct RemoteServer<S: RemoteStream = TcpStream> {
+    stream: S,
+    previous: Option<FrameSnapshot>,
+    font_atlas_sent: bool,
+    incoming: Vec<u8>,
+}
+
+impl RemoteServer<TcpStream> {
+    /// Waits for a viewer to connect to `listener`.
+    pub fn accept(listener: &TcpListener) -> io::Result<Self> {
+        let (stream, _) = listener.accept()?;
+        stream.set_nodelay(true)?;
+        Ok(Self::new(stream))
+    }
+}
+
+impl<S: RemoteStream> RemoteServer<S> {
+    /// Creates a server streaming over an already connected socket.
+    pub fn new(stream: S) -> Self {
+        RemoteServer {
+            stream,
+            previous: None,
+            font_atlas_sent: false,
+            incoming: Vec::new(),
+        }
+    }
+
+    /// Uploads the font atlas as RGBA32 pixels, unless it was already sent on this connection.
+    ///
+    /// The viewer has to make the atlas available to its renderer under the same texture id.
+    pub fn send_font_atlas(&mut self, fonts: &mut FontAtlas) -> io::Result<()> {
+        if self.font_atlas_sent {
+            return Ok(());
+        }
+        let texture_id = fonts.tex_id;
+        let texture = fonts.build_rgba32_texture();
+        let mut payload = Vec::with_capacity(16 + texture.data.len());
+        payload.extend_from_slice(&(texture_id.id() as u64).to_le_bytes());
+        payload.extend_from_slice(&texture.width.to_le_bytes());
+        payload.extend_from_slice(&texture.height.to_le_bytes());
+        payload.extend_from_slice(texture.data);
+        write_message(&mut self.stream, MSG_FONT_ATLAS, &payload)?;
+        self.font_atlas_sent = true;
+        Ok(())
+    }
+
+    /// Sends a rendered frame.
+    ///
+    /// Only the vertices which differ from the previously sent frame are transmitted.
+    pub fn send_frame(&mut self, draw_data: &DrawData) -> io::Result<()> {
+        let snapshot = FrameSnapshot::from(draw_data);
+        match &self.previous {
+            Some(previous) => {
+                let payload = encode_delta(previous, &snapshot);
+                write_message(&mut self.stream, MSG_FRAME_DELTA, &payload)?;
+            }
+            None => write_message(&mut self.stream, MSG_FRAME, &snapshot.to_bytes())?,
+        }
+        self.previous = Some(snapshot);
+        Ok(())
+    }
+
+    /// Queues all input received from the viewer so far into `io`, without blocking.
+    ///
+    /// Returns the number of events which were applied. Fails with
+    /// [`io::ErrorKind::UnexpectedEof`] once the viewer disconnected.
+    ///
+    /// A message which can't be decoded is dropped and reported as an
+    /// [`io::ErrorKind::InvalidData`] error. If events before it were applied, they are counted
+    /// first, and the error is returned by the next call.
+    pub fn poll_input(&mut self, io: &mut Io) -> io::Result<usize> {
+        self.stream.set_nonblocking(true)?;
+        let mut buf = [0; 4096];
+        let read = loop {
+            match self.stream.read(&mut buf) {
+                Ok(0) => break Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(n) => self.incoming.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => break Err(e),
+            }
+        };
+        self.stream.set_nonblocking(false)?;
+
+        let mut applied = 0;
+        let mut consumed = 0;
+        let result = loop {
+            let (kind, payload, len) = match split_message(&self.incoming[consumed..]) {
+                Ok(Some(message)) => message,
+                Ok(None) => break read.map(|()| applied),
+                Err(err) => {
+                    // The stream can't be resynchronized after a bad header.
+                    consumed = self.incoming.len();
+                    break Err(err.into());
+                }
+            };
+            let event = match kind {
+                MSG_INPUT => RemoteInput::decode(&mut Reader { bytes: payload }),
+                kind => Err(RemoteError::UnexpectedMessage(kind)),
+            };
+            match event {
+                Ok(event) => event.apply(io),
+                Err(_) if applied > 0 => break Ok(applied),
+                Err(err) => {
+                    consumed += len;
+                    break Err(err.into());
+                }
+            }
+            applied += 1;
+            consumed += len;
+        };
+        self.incoming.drain(..consumed);
+        result
+    }
+
+    /// Returns the underlying socket.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+/// A message received by a [`RemoteViewer`].
+#[derive(Debug)]
+pub enum RemoteMessage<'a> {
+    /// The font atlas, as RGBA32 pixels.
+    FontAtlas {
+        texture_id: TextureId,
+        width: u32,
+        height: u32,
+        data: Vec<u8>,
+    },
+    /// A new frame, ready to be rendered.
+    Frame(&'a FrameSnapshot),
+}
+
+/// Receives frames from a [`RemoteServer`] and sends input back.
+pub struct RemoteViewer<S: RemoteStream = TcpStream> {
+    stream: S,
+    frame: Option<FrameSnapshot>,
+}
+
+impl RemoteViewer<TcpStream> {
+    /// Connects to a server.
+    pub fn connect(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let stream = TcpStream::connect(addr)?;
+        stream.set_nodelay(true)?;
+        Ok(Self::new(stream))
+    }
+}
+
+impl<S: RemoteStream> RemoteViewer<S> {
+    /// Creates a viewer receiving over an already connected socket.
+    pub fn new(stream: S) -> Self {
+        RemoteViewer {
+            stream,
+            frame: None,
+        }
+    }
+
+    /// Blocks until the next message from the server arrives.
+    pub fn recv(&mut self) -> io::Result<RemoteMessage<'_>> {
+        let mut header = [0; 5];
+        self.stream.read_exact(&mut header)?;
+        let len = message_len(&header)?;
+        // Grow the buffer as data arrives, rather than trusting the header for the allocation.
+        let mut payload = Vec::new();
+        (&mut self.stream)
+            .take(len as u64)
+            .read_to_end(&mut payload)?;
+        if payload.len() < len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        let mut r = Reader { bytes: &payload };
+        match header[0] {
+            MSG_FONT_ATLAS => {
+                let texture_id = u64::from_le_bytes(r.array()?);
+                let texture_id = usize::try_from(texture_id)
+                    .map_err(|_| SnapshotDecodeError::InvalidTextureId(texture_id))?;
+                let width = u32::from_le_bytes(r.array()?);
+                let height = u32::from_le_bytes(r.array()?);
+                let pixels = (width as usize).checked_mul(height as usize);
+                if pixels.and_then(|pixels| pixels.checked_mul(4)) != Some(r.bytes.len()) {
+                    return Err(RemoteError::InvalidFontAtlas.into());
+                }
+                Ok(RemoteMessage::FontAtlas {
+                    texture_id: TextureId::new(texture_id),
+                    width,
+                    height,
+                    data: r.bytes.to_vec(),
+                })
+            }
+            MSG_FRAME => {
+                let frame = FrameSnapshot::from_bytes(&payload).map_err(RemoteError::from)?;
+                Ok(RemoteMessage::Frame(self.frame.insert(frame)))
+            }
+            MSG_FRAME_DELTA => {
+                let previous = self
+                    .frame
+                    .as_ref()
+                    .ok_or(RemoteError::UnexpectedMessage(MSG_FRAME_DELTA))?;
+                let frame = decode_delta(previous, &mut r)?;
+                Ok(RemoteMessage::Frame(self.frame.insert(frame)))
+            }
+            kind => Err(RemoteError::UnexpectedMessage(kind).into()),
+        }
+    }
+
+    /// The most recently received frame.
+    pub fn frame(&self) -> Option<&FrameSnapshot> {
+        self.frame.as_ref()
+    }
+
+    /// Sends an input event to the server.
+    pub fn send_input(&mut self, event: RemoteInput) -> io::Result<()> {
+        let mut payload = Vec::with_capacity(9);
+        event.encode(&mut payload);
+        write_message(&mut self.stream, MSG_INPUT, &payload)
+    }
+
+    /// Returns the underlying socket.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+/// Encodes `frame` as the changes since `previous`.
+///
+/// The payload is the frame without any vertices, followed by the vertex count and the changed
+/// runs of vertices of each draw list.
+fn encode_delta(previous: &FrameSnapshot, frame: &FrameSnapshot) -> Vec<u8> {
+    let mut geometry = frame.clone();
+    for list in &mut geometry.draw_lists {
+        list.vtx_buffer.clear();
+    }
+    let geometry = geometry.to_bytes();
+
+    let mut out = Vec::with_capacity(geometry.len() + 64);
+    put_len(&mut out, geometry.len());
+    out.extend_from_slice(&geometry);
+    for (i, list) in frame.draw_lists.iter().enumerate() {
+        let old: &[DrawVert] = previous
+            .draw_lists
+            .get(i)
+            .map_or(&[], |l| l.vtx_buffer.as_slice());
+        let new = &list.vtx_buffer;
+        put_len(&mut out, new.len());
+
+        let mut runs = Vec::new();
+        let mut start = None;
+        for (j, vtx) in new.iter().enumerate() {
+            let changed = old.get(j) != Some(vtx);
+            match (changed, start) {
+                (true, None) => start = Some(j),
+                (false, Some(s)) => {
+                    runs.push(s..j);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(s) = start {
+            runs.push(s..new.len());
+        }
+
+        put_len(&mut out, runs.len());
+        for run in runs {
+            put_len(&mut out, run.start);
+            put_len(&mut out, run.len());
+            for vtx in &new[run] {
+                put_vertex(&mut out, vtx);
+            }
+        }
+    }
+    out
+}
+
+fn decode_delta(
+    previous: &FrameSnapshot,
+    r: &mut Reader<'_>,
+) -> Result<FrameSnapshot, RemoteError> {
+    let geometry_len = r.len()?;
+    let mut frame = FrameSnapshot::from_bytes_unvalidated(r.take(geometry_len)?)?;
+    for (i, list) in frame.draw_lists.iter_mut().enumerate() {
+        let old: &[DrawVert] = previous
+            .draw_lists
+            .get(i)
+            .map_or(&[], |l| l.vtx_buffer.as_slice());
+        let vtx_count = r.len()?;
+        // Vertices past the previous frame must all be sent in this delta.
+        if vtx_count.saturating_sub(old.len()) > r.bytes.len() / VERTEX_SIZE {
+            return Err(RemoteError::InvalidDelta);
+        }
+        let mut filled = vtx_count.min(old.len());
+        list.vtx_buffer.extend_from_slice(&old[..filled]);
+        list.vtx_buffer.resize(
+            vtx_count,
+            DrawVert {
+                pos: [0.0; 2],
+                uv: [0.0; 2],
+                col: [0; 4],
+            },
+        );
+
+        for _ in 0..r.len()? {
+            let start = r.len()?;
+            let len = r.len()?;
+            let run = list
+                .vtx_buffer
+                .get_mut(start..start.saturating_add(len))
+                .ok_or(RemoteError::InvalidDelta)?;
+            for vtx in run {
+                *vtx = r.vertex()?;
+            }
+            if start <= filled {
+                filled = filled.max(start + len);
+            }
+        }
+        if filled < vtx_count {
+            return Err(RemoteError::InvalidDelta);
+        }
+    }
+    if !r.bytes.is_empty() {
+        return Err(RemoteError::InvalidDelta);
+    }
+    frame.validate()?;
+    Ok(frame)
+}
+
+fn put_f32s(out: &mut Vec<u8>, values: &[f32]) {
+    for v in values {
+        out.extend_from_slice(&v.to_le_bytes());
+    }
+}
+
+fn write_message(stream: &mut impl Write, kind: u8, payload: &[u8]) -> io::Result<()> {
+    let mut header = [kind, 0, 0, 0, 0];
+    header[1..].copy_from_slice(&(payload.len() as u32).to_le_bytes());
+    stream.write_all(&header)?;
+    stream.write_all(payload)?;
+    stream.flush()
+}
+
+/// Returns the payload length of a message header.
+fn message_len(header: &[u8]) -> Result<usize, RemoteError> {
+    let len = u32::from_le_bytes(header[1..5].try_into().unwrap()) as usize;
+    if len > MAX_MESSAGE_LEN {
+        return Err(RemoteError::MessageTooLarge(len));
+    }
+    Ok(len)
+}
+
+/// The kind, payload and total length of a message.
+type Message<'a> = (u8, &'a [u8], usize);
+
+/// Returns the first complete message in `bytes`.
+fn split_message(bytes: &[u8]) -> Result<Option<Message<'_>>, RemoteError> {
+    let Some(header) = bytes.get(..5) else {
+        return Ok(None);
+    };
+    let len = message_len(header)?;
+    Ok(bytes
+        .get(5..5 + len)
+        .map(|payload| (header[0], payload, 5 + len)))
+}
+
+/// A protocol error on a remote connection.
+///
+/// These are reported as [`io::ErrorKind::InvalidData`] errors wrapping this type.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum RemoteError {
+    /// A frame could not be decoded.
+    Snapshot(SnapshotDecodeError),
+    /// A vertex delta doesn't fit the previous frame.
+    InvalidDelta,
+    /// The pixels of a font atlas don't match its size.
+    InvalidFontAtlas,
+    /// An input event has an unknown type or value.
+    InvalidInput(u8),
+    /// A message of this kind isn't expected on this side of the connection.
+    UnexpectedMessage(u8),
+    /// A message header announces more bytes than any valid message has.
+    MessageTooLarge(usize),
+}
+
+impl From<SnapshotDecodeError> for RemoteError {
+    fn from(err: SnapshotDecodeError) -> Self {
+        RemoteError::Snapshot(err)
+    }
+}
+
+impl From<SnapshotDecodeError> for io::Error {
+    fn from(err: SnapshotDecodeError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, RemoteError::from(err))
+    }
+}
+
+impl From<RemoteError> for io::Error {
+    fn from(err: RemoteError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, err)
+    }
+}
+
+impl fmt::Display for RemoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemoteError::Snapshot(err) => err.fmt(f),
+            RemoteError::InvalidDelta => f.pad("Invalid frame delta"),
+            RemoteError::InvalidFontAtlas => f.pad("Font atlas size doesn't match its pixels"),
+            RemoteError::InvalidInput(tag) => write!(f, "Invalid input event of type {}", tag),
+            RemoteError::UnexpectedMessage(kind) => {
+                write!(f, "Unexpected message of type {}", kind)
+            }
+            RemoteError::MessageTooLarge(len) => write!(f, "Message of {} bytes is too large", len),
+        }
+    }
+}
+
+impl std::error::Error for RemoteError {}
+
+#[test]
+fn test_remote_loopback() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut viewer = RemoteViewer::connect(listener.local_addr().unwrap()).unwrap();
+    let mut server = RemoteServer::accept(&listener).unwrap();
+
+    server.send_font_atlas(ctx.fonts()).unwrap();
+    // The atlas is only sent once
+    server.send_font_atlas(ctx.fonts()).unwrap();
+    match viewer.recv().unwrap() {
+        RemoteMessage::FontAtlas {
+            width,
+            height,
+            data,
+            ..
+        } => assert_eq!(data.len(), (width * height * 4) as usize),
+        other => panic!("unexpected message {:?}", other),
+    }
+
+    let mut sent = Vec::new();
+    for offset in [0.0, 0.0, 30.0] {
+        let ui = ctx.new_frame();
+        let draw_list = ui.get_background_draw_list();
+        draw_list
+            .add_rect([10.0, 10.0], [20.0, 20.0], [1.0, 0.0, 0.0, 1.0])
+            .filled(true)
+            .build();
+        draw_list
+            .add_rect([offset, 40.0], [offset + 10.0, 50.0], [0.0, 1.0, 0.0, 1.0])
+            .filled(true)
+            .build();
+        drop(draw_list);
+        let draw_data = ctx.render();
+        server.send_frame(draw_data).unwrap();
+        sent.push(FrameSnapshot::from(draw_data));
+    }
+    for frame in &sent {
+        match viewer.recv().unwrap() {
+            RemoteMessage::Frame(received) => assert_eq!(received, frame),
+            other => panic!("unexpected message {:?}", other),
+        }
+    }
+
+    viewer
+        .send_input(RemoteInput::MousePos([12.0, 34.0]))
+        .unwrap();
+    viewer.send_input(RemoteInput::Key(Key::A, true)).unwrap();
+    viewer.send_input(RemoteInput::Char('é')).unwrap();
+    let mut applied = 0;
+    while applied < 3 {
+        applied += server.poll_input(ctx.io_mut()).unwrap();
+    }
+    ctx.io_mut().config_input_trickle_event_queue = false;
+    let ui = ctx.new_frame();
+    assert_eq!(ui.io().mouse_pos, [12.0, 34.0]);
+    assert!(ui.is_key_down(Key::A));
+
+    drop(viewer);
+    let result = loop {
+        match server.poll_input(ctx.io_mut()) {
+            Ok(_) => continue,
+            Err(e) => break e,
+        }
+    };
+    assert_eq!(result.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_remote_delta_only_sends_changes() {
+    use crate::render::draw_data::DrawCmdParams;
+    use crate::render::snapshot::{DrawListSnapshot, SnapshotCmd};
+
+    let vertex = |x: f32| DrawVert {
+        pos: [x, 0.0],
+        uv: [0.0, 0.0],
+        col: [255; 4],
+    };
+    let frame = |vertices: Vec<DrawVert>| FrameSnapshot {
+        display_size: [100.0, 100.0],
+        framebuffer_scale: [1.0, 1.0],
+        draw_lists: vec![DrawListSnapshot {
+            idx_buffer: (0..vertices.len() as u16).collect(),
+            commands: vec![SnapshotCmd::Elements {
+                count: vertices.len(),
+                cmd_params: DrawCmdParams {
+                    clip_rect: [0.0, 0.0, 100.0, 100.0],
+                    texture_id: TextureId::new(0),
+                    vtx_offset: 0,
+                    idx_offset: 0,
+                },
+            }],
+            vtx_buffer: vertices,
+        }],
+        ..Default::default()
+    };
+    let previous = frame((0..300).map(|x| vertex(x as f32)).collect());
+    let mut vertices = previous.draw_lists[0].vtx_buffer.clone();
+    vertices[150] = vertex(-1.0);
+    vertices.push(vertex(-2.0));
+    let next = frame(vertices);
+
+    let delta = encode_delta(&previous, &next);
+    assert!(delta.len() < next.to_bytes().len() / 4);
+    let decoded = decode_delta(&previous, &mut Reader { bytes: &delta }).unwrap();
+    assert_eq!(decoded, next);
+}
+
+#[test]
+fn test_remote_rejects_bad_messages() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut viewer = RemoteViewer::connect(listener.local_addr().unwrap()).unwrap();
+    let mut server = RemoteServer::accept(&listener).unwrap();
+
+    // An input event with an unknown type between two valid ones.
+    let mut payload = Vec::new();
+    RemoteInput::MousePos([1.0, 2.0]).encode(&mut payload);
+    write_message(&mut viewer.stream, MSG_INPUT, &payload).unwrap();
+    write_message(&mut viewer.stream, MSG_INPUT, &[99]).unwrap();
+    viewer.send_input(RemoteInput::Char('a')).unwrap();
+    let mut results = Vec::new();
+    while results.len() < 3 {
+        match server.poll_input(ctx.io_mut()) {
+            Ok(0) => {}
+            Ok(applied) => results.push(Ok(applied)),
+            Err(err) => results.push(Err(err.kind())),
+        }
+    }
+    assert_eq!(results, [Ok(1), Err(io::ErrorKind::InvalidData), Ok(1)]);
+
+    // A delta announcing more vertices than it carries.
+    let mut previous = FrameSnapshot::default();
+    previous.draw_lists.push(Default::default());
+    let geometry = previous.to_bytes();
+    let mut delta = Vec::new();
+    put_len(&mut delta, geometry.len());
+    delta.extend_from_slice(&geometry);
+    put_len(&mut delta, u32::MAX as usize);
+    put_len(&mut delta, 0);
+    assert_eq!(
+        decode_delta(&previous, &mut Reader { bytes: &delta }),
+        Err(RemoteError::InvalidDelta)
+    );
+
+    // A delta whose draw command uses a vertex the frame doesn't have.
+    let mut next = previous.clone();
+    next.draw_lists[0].idx_buffer.push(0);
+    next.draw_lists[0]
+        .commands
+        .push(crate::render::snapshot::SnapshotCmd::Elements {
+            count: 1,
+            cmd_params: crate::render::draw_data::DrawCmdParams {
+                clip_rect: [0.0; 4],
+                texture_id: TextureId::new(0),
+                vtx_offset: 0,
+                idx_offset: 0,
+            },
+        });
+    let delta = encode_delta(&previous, &next);
+    assert_eq!(
+        decode_delta(&previous, &mut Reader { bytes: &delta }),
+        Err(RemoteError::Snapshot(SnapshotDecodeError::InvalidIndex(0)))
+    );
+
+    // A font atlas with fewer pixels than its size.
+    let mut payload = Vec::new();
+    payload.extend_from_slice(&0u64.to_le_bytes());
+    payload.extend_from_slice(&2u32.to_le_bytes());
+    payload.extend_from_slice(&2u32.to_le_bytes());
+    payload.extend_from_slice(&[0; 12]);
+    write_message(&mut server.stream, MSG_FONT_ATLAS, &payload).unwrap();
+    let err = viewer.recv().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    // A header announcing a huge message.
+    server
+        .stream
+        .write_all(&[MSG_FRAME, 255, 255, 255, 255])
+        .unwrap();
+    let err = viewer.recv().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
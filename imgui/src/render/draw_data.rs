@@ -1,3 +1,5 @@
+use std::any::Any;
+use std::marker::PhantomData;
 use std::slice;
 
 use crate::internal::{ImVector, RawCast, RawWrapper};
@@ -221,8 +223,8 @@ pub struct DrawCmdIterator<'a> {
     iter: std::slice::Iter<'a, sys::ImDrawCmd>,
 }
 
-impl Iterator for DrawCmdIterator<'_> {
-    type Item = DrawCmd;
+impl<'a> Iterator for DrawCmdIterator<'a> {
+    type Item = DrawCmd<'a>;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
@@ -237,6 +239,14 @@ impl Iterator for DrawCmdIterator<'_> {
                 Some(raw_callback) if raw_callback as usize == -1isize as usize => {
                     DrawCmd::ResetRenderState
                 }
+                Some(raw_callback)
+                    if raw_callback as usize == typed_callback as *const () as usize =>
+                {
+                    DrawCmd::UserCallback {
+                        callback: DrawCallback::from_raw_cmd(cmd),
+                        cmd_params,
+                    }
+                }
                 Some(raw_callback) => DrawCmd::RawCallback {
                     callback: raw_callback,
                     raw_cmd: cmd,
@@ -263,19 +273,178 @@ pub struct DrawCmdParams {
 }
 
 /// A draw command
-pub enum DrawCmd {
+pub enum DrawCmd<'a> {
     Elements {
         /// The number of indices used for this draw command
         count: usize,
         cmd_params: DrawCmdParams,
     },
     ResetRenderState,
+    /// A callback registered through one of the typed
+    /// [`DrawListMut`](crate::DrawListMut) methods, such as
+    /// [`DrawListMut::add_user_callback`](crate::DrawListMut::add_user_callback)
+    /// or [`DrawListMut::set_blend_mode`](crate::DrawListMut::set_blend_mode).
+    UserCallback {
+        callback: DrawCallback<'a>,
+        cmd_params: DrawCmdParams,
+    },
     RawCallback {
         callback: unsafe extern "C" fn(*const sys::ImDrawList, cmd: *const sys::ImDrawCmd),
         raw_cmd: *const sys::ImDrawCmd,
     },
 }
 
+/// Blend mode requested by a [`DrawCallback::SetBlendMode`] marker.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum BlendMode {
+    /// Regular alpha blending (`src * src_alpha + dst * (1 - src_alpha)`), which is what Dear
+    /// ImGui expects by default.
+    #[default]
+    Alpha,
+    /// Blending for colors which already have alpha multiplied in (`src + dst * (1 - src_alpha)`).
+    PremultipliedAlpha,
+    /// Additive blending (`src * src_alpha + dst`).
+    Additive,
+    /// No blending, the source color replaces the destination.
+    Opaque,
+}
+
+impl BlendMode {
+    pub(crate) const VARIANTS: [BlendMode; 4] = [
+        BlendMode::Alpha,
+        BlendMode::PremultipliedAlpha,
+        BlendMode::Additive,
+        BlendMode::Opaque,
+    ];
+}
+
+/// Texture filtering requested by a [`DrawCallback::SetSampler`] marker.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum SamplerFilter {
+    /// Linear filtering, which is what Dear ImGui expects by default.
+    #[default]
+    Linear,
+    /// Nearest-neighbour filtering, useful for pixel art.
+    Nearest,
+}
+
+impl SamplerFilter {
+    pub(crate) const VARIANTS: [SamplerFilter; 2] = [SamplerFilter::Linear, SamplerFilter::Nearest];
+}
+
+/// The payload of a [`DrawCmd::UserCallback`].
+///
+/// The marker variants carry state changes that the renderer is expected to apply to all
+/// following draw commands of the draw list, until it sees another marker or a
+/// [`DrawCmd::ResetRenderState`].
+#[derive(Debug)]
+pub enum DrawCallback<'a> {
+    /// Switch the blend mode used for subsequent draw commands.
+    SetBlendMode(BlendMode),
+    /// Switch the texture filtering used for subsequent draw commands.
+    SetSampler(SamplerFilter),
+    /// A closure registered with
+    /// [`DrawListMut::add_user_callback`](crate::DrawListMut::add_user_callback).
+    User(UserCallbackHandle<'a>),
+}
+
+// The payload of a typed callback is stored directly in `ImDrawCmd::UserCallbackData`. The low
+// two bits tag the kind of payload: boxed closures are at least 4-byte aligned so their tag is
+// always zero, while markers store their value in the remaining bits.
+const CALLBACK_TAG_MASK: usize = 0b11;
+const CALLBACK_TAG_USER: usize = 0;
+const CALLBACK_TAG_BLEND_MODE: usize = 1;
+const CALLBACK_TAG_SAMPLER: usize = 2;
+
+pub(crate) type BoxedUserCallback = Box<dyn FnOnce(&mut dyn Any) + 'static>;
+
+impl DrawCallback<'_> {
+    fn from_raw_cmd(cmd: &sys::ImDrawCmd) -> DrawCallback<'_> {
+        let data = cmd.UserCallbackData as usize;
+        let value = data >> 2;
+        match data & CALLBACK_TAG_MASK {
+            CALLBACK_TAG_BLEND_MODE => DrawCallback::SetBlendMode(
+                BlendMode::VARIANTS.get(value).copied().unwrap_or_default(),
+            ),
+            CALLBACK_TAG_SAMPLER => DrawCallback::SetSampler(
+                SamplerFilter::VARIANTS
+                    .get(value)
+                    .copied()
+                    .unwrap_or_default(),
+            ),
+            _ => DrawCallback::User(UserCallbackHandle {
+                raw_cmd: cmd,
+                _phantom: PhantomData,
+            }),
+        }
+    }
+
+    pub(crate) fn blend_mode_data(mode: BlendMode) -> *mut std::ffi::c_void {
+        ((mode as usize) << 2 | CALLBACK_TAG_BLEND_MODE) as *mut std::ffi::c_void
+    }
+
+    pub(crate) fn sampler_data(filter: SamplerFilter) -> *mut std::ffi::c_void {
+        ((filter as usize) << 2 | CALLBACK_TAG_SAMPLER) as *mut std::ffi::c_void
+    }
+
+    pub(crate) fn user_data(callback: BoxedUserCallback) -> *mut std::ffi::c_void {
+        // The outer box makes this a thin pointer, and guarantees the alignment used by the tag.
+        let data = Box::into_raw(Box::new(callback));
+        debug_assert_eq!(data as usize & CALLBACK_TAG_MASK, CALLBACK_TAG_USER);
+        data as *mut std::ffi::c_void
+    }
+}
+
+/// A closure registered with
+/// [`DrawListMut::add_user_callback`](crate::DrawListMut::add_user_callback), waiting to be run by
+/// the renderer.
+pub struct UserCallbackHandle<'a> {
+    raw_cmd: *const sys::ImDrawCmd,
+    _phantom: PhantomData<&'a DrawList>,
+}
+
+impl UserCallbackHandle<'_> {
+    /// Runs the callback, passing it a renderer-provided context.
+    ///
+    /// The callback is consumed the first time it is run, so invoking it again (for example when
+    /// rendering the same draw data twice) does nothing.
+    pub fn invoke(self, context: &mut dyn Any) {
+        unsafe { run_user_callback(self.raw_cmd, context) }
+    }
+}
+
+impl std::fmt::Debug for UserCallbackHandle<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UserCallbackHandle")
+            .field("raw_cmd", &self.raw_cmd)
+            .finish()
+    }
+}
+
+unsafe fn run_user_callback(cmd: *const sys::ImDrawCmd, context: &mut dyn Any) {
+    // We are modifying through a C const pointer, but that should be harmless.
+    let cmd = &mut *(cmd as *mut sys::ImDrawCmd);
+    let data = cmd.UserCallbackData as usize;
+    if data == 0 || data & CALLBACK_TAG_MASK != CALLBACK_TAG_USER {
+        return;
+    }
+    // Consume the pointer and leave a NULL behind so that the closure can't run twice.
+    cmd.UserCallbackData = std::ptr::null_mut();
+    let callback = Box::from_raw(data as *mut BoxedUserCallback);
+    callback(context);
+}
+
+/// The `ImDrawCallback` used for all typed callbacks.
+///
+/// Renderers that only know about [`DrawCmd::RawCallback`] may call this directly, in which case
+/// markers are ignored and user callbacks receive `&mut ()` as their context.
+pub(crate) unsafe extern "C" fn typed_callback(
+    _parent_list: *const sys::ImDrawList,
+    cmd: *const sys::ImDrawCmd,
+) {
+    run_user_callback(cmd, &mut ());
+}
+
 /// A single vertex
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -320,6 +489,16 @@ pub struct OwnedDrawData {
 }
 
 impl OwnedDrawData {
+    /// Takes ownership of a heap-allocated `ImDrawData` and the draw lists it points to.
+    ///
+    /// # Safety
+    /// `draw_data` must be null or allocated by `ImDrawData_ImDrawData`, and its `CmdLists`
+    /// buffer and draw lists must have been allocated by Dear ImGui's allocator.
+    #[inline]
+    pub(crate) unsafe fn from_raw(draw_data: *mut sys::ImDrawData) -> Self {
+        OwnedDrawData { draw_data }
+    }
+
     /// If this struct contains a `DrawData` object, then this function returns a reference to it.
     ///
     /// Otherwise, this struct is empty and so this function returns `None`.
@@ -382,6 +561,10 @@ impl Drop for OwnedDrawData {
                         }
                     }
                     sys::igMemFree((*self.draw_data).CmdLists.Data as *mut std::ffi::c_void);
+                    // The destructor of `ImDrawData` would otherwise free the list a second time
+                    (*self.draw_data).CmdLists.Data = std::ptr::null_mut();
+                    (*self.draw_data).CmdLists.Size = 0;
+                    (*self.draw_data).CmdLists.Capacity = 0;
                 }
                 sys::ImDrawData_destroy(self.draw_data);
                 self.draw_data = std::ptr::null_mut();
@@ -454,3 +637,62 @@ fn test_owneddrawdata_from_drawdata() {
         owned_draw_data_raw.OwnerViewport
     );
 }
+
+#[test]
+#[cfg(test)]
+fn test_drawcmd_user_callbacks() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ran = Rc::new(Cell::new(0u32));
+
+    let ui = ctx.new_frame();
+    {
+        let draw_list = ui.get_background_draw_list();
+        draw_list.set_blend_mode(BlendMode::Additive);
+        draw_list.set_sampler(SamplerFilter::Nearest);
+        let ran = ran.clone();
+        draw_list
+            .add_user_callback(move |context| {
+                let counter = context.downcast_mut::<u32>().unwrap();
+                ran.set(*counter);
+                *counter += 1;
+            })
+            .build();
+    }
+    let draw_data = ctx.render();
+
+    let mut blend_modes = Vec::new();
+    let mut samplers = Vec::new();
+    let mut context = 41u32;
+    for draw_list in draw_data.draw_lists() {
+        for cmd in draw_list.commands() {
+            if let DrawCmd::UserCallback { callback, .. } = cmd {
+                match callback {
+                    DrawCallback::SetBlendMode(mode) => blend_modes.push(mode),
+                    DrawCallback::SetSampler(filter) => samplers.push(filter),
+                    DrawCallback::User(callback) => callback.invoke(&mut context),
+                }
+            }
+        }
+    }
+    assert_eq!(blend_modes, [BlendMode::Additive]);
+    assert_eq!(samplers, [SamplerFilter::Nearest]);
+    assert_eq!(ran.get(), 41);
+    assert_eq!(context, 42);
+
+    // The callback is consumed after running once
+    for draw_list in draw_data.draw_lists() {
+        for cmd in draw_list.commands() {
+            if let DrawCmd::UserCallback {
+                callback: DrawCallback::User(callback),
+                ..
+            } = cmd
+            {
+                callback.invoke(&mut context);
+            }
+        }
+    }
+    assert_eq!(context, 42);
+}
@@ -0,0 +1,300 @@
+use crate::internal::RawWrapper;
+use crate::render::draw_data::{DrawCallback, DrawCmd, DrawCmdParams, DrawData, DrawIdx, DrawVert};
+
+/// All draw lists of a frame merged into a single vertex and index buffer.
+///
+/// Many graphics APIs are most efficient when a frame is uploaded in one go. The commands of a
+/// flattened frame have their `vtx_offset` and `idx_offset` rebased to point into the merged
+/// buffers, so they can be drawn exactly like the commands of a single draw list.
+///
+/// Callbacks are kept and borrow from the draw data, see [`FlattenedCmd`].
+#[derive(Debug, Default)]
+pub struct FlattenedDrawData<'a> {
+    /// Upper-left position of the viewport to render.
+    pub display_pos: [f32; 2],
+    /// Size of the viewport to render.
+    pub display_size: [f32; 2],
+    /// Amount of pixels for each unit of display_size.
+    pub framebuffer_scale: [f32; 2],
+    pub vtx_buffer: Vec<DrawVert>,
+    pub idx_buffer: Vec<DrawIdx>,
+    pub commands: Vec<FlattenedCmd<'a>>,
+}
+
+/// A draw command of a [`FlattenedDrawData`].
+///
+/// This is a [`DrawCmd`] which also remembers the draw list of raw callbacks, as they can't be
+/// invoked without it.
+#[derive(Debug)]
+pub enum FlattenedCmd<'a> {
+    Elements {
+        /// The number of indices used for this draw command
+        count: usize,
+        cmd_params: DrawCmdParams,
+    },
+    ResetRenderState,
+    /// See [`DrawCmd::UserCallback`].
+    UserCallback {
+        callback: DrawCallback<'a>,
+        cmd_params: DrawCmdParams,
+    },
+    /// See [`DrawCmd::RawCallback`]. The callback should be invoked with `raw_list` and `raw_cmd`.
+    RawCallback {
+        callback: unsafe extern "C" fn(*const sys::ImDrawList, cmd: *const sys::ImDrawCmd),
+        raw_list: *const sys::ImDrawList,
+        raw_cmd: *const sys::ImDrawCmd,
+    },
+}
+
+impl DrawData {
+    /// Merges all draw lists into a single vertex and index buffer.
+    pub fn flatten(&self) -> FlattenedDrawData<'_> {
+        let mut flattened = FlattenedDrawData {
+            display_pos: self.display_pos,
+            display_size: self.display_size,
+            framebuffer_scale: self.framebuffer_scale,
+            vtx_buffer: Vec::with_capacity(self.total_vtx_count.max(0) as usize),
+            idx_buffer: Vec::with_capacity(self.total_idx_count.max(0) as usize),
+            commands: Vec::new(),
+        };
+        for draw_list in self.draw_lists() {
+            let vtx_base = flattened.vtx_buffer.len();
+            let idx_base = flattened.idx_buffer.len();
+            let rebase = |mut cmd_params: DrawCmdParams| {
+                cmd_params.vtx_offset += vtx_base;
+                cmd_params.idx_offset += idx_base;
+                cmd_params
+            };
+            let raw_list = unsafe { draw_list.raw() as *const sys::ImDrawList };
+            flattened
+                .vtx_buffer
+                .extend_from_slice(draw_list.vtx_buffer());
+            flattened
+                .idx_buffer
+                .extend_from_slice(draw_list.idx_buffer());
+            flattened
+                .commands
+                .extend(draw_list.commands().map(|cmd| match cmd {
+                    DrawCmd::Elements { count, cmd_params } => FlattenedCmd::Elements {
+                        count,
+                        cmd_params: rebase(cmd_params),
+                    },
+                    DrawCmd::ResetRenderState => FlattenedCmd::ResetRenderState,
+                    DrawCmd::UserCallback {
+                        callback,
+                        cmd_params,
+                    } => FlattenedCmd::UserCallback {
+                        callback,
+                        cmd_params: rebase(cmd_params),
+                    },
+                    DrawCmd::RawCallback { callback, raw_cmd } => FlattenedCmd::RawCallback {
+                        callback,
+                        raw_list,
+                        raw_cmd,
+                    },
+                }));
+        }
+        flattened
+    }
+}
+
+impl FlattenedDrawData<'_> {
+    /// Moves all vertices and clip rects by `offset`.
+    pub fn translate(&mut self, offset: [f32; 2]) {
+        for vtx in &mut self.vtx_buffer {
+            vtx.pos[0] += offset[0];
+            vtx.pos[1] += offset[1];
+        }
+        for cmd in &mut self.commands {
+            if let FlattenedCmd::Elements { cmd_params, .. }
+            | FlattenedCmd::UserCallback { cmd_params, .. } = cmd
+            {
+                let clip = &mut cmd_params.clip_rect;
+                clip[0] += offset[0];
+                clip[1] += offset[1];
+                clip[2] += offset[0];
+                clip[3] += offset[1];
+            }
+        }
+    }
+
+    /// Translates the frame so that `display_pos` is at the origin.
+    ///
+    /// Afterwards the projection matrix only depends on `display_size`, and clip rects can be
+    /// used as scissor rects after scaling by `framebuffer_scale`.
+    pub fn translate_to_origin(&mut self) {
+        let [x, y] = self.display_pos;
+        self.translate([-x, -y]);
+        self.display_pos = [0.0, 0.0];
+    }
+
+    /// Converts the vertex colors from sRGB to linear, for renderers drawing into an sRGB
+    /// framebuffer. Alpha is left untouched.
+    ///
+    /// The colors stay 8-bit, so dark tones lose precision: the sRGB values 0 to 6 all become 0
+    /// and 7 to 17 all become 1 in linear. Converting in a shader avoids this.
+    pub fn convert_colors_to_linear(&mut self) {
+        for vtx in &mut self.vtx_buffer {
+            for c in &mut vtx.col[..3] {
+                *c = to_u8(srgb_to_linear(*c as f32 / 255.0));
+            }
+        }
+    }
+
+    /// Converts the vertex colors from linear to sRGB. Alpha is left untouched.
+    ///
+    /// As with [`convert_colors_to_linear`](Self::convert_colors_to_linear), the colors stay
+    /// 8-bit, so converting back and forth doesn't restore dark tones.
+    pub fn convert_colors_to_srgb(&mut self) {
+        for vtx in &mut self.vtx_buffer {
+            for c in &mut vtx.col[..3] {
+                *c = to_u8(linear_to_srgb(*c as f32 / 255.0));
+            }
+        }
+    }
+
+    /// Returns a 32-bit copy of the index buffer with each command's `vtx_offset` added in.
+    ///
+    /// This is meant for renderers which can't use a base vertex when drawing: with these
+    /// indices, the `vtx_offset` of the commands must be ignored.
+    pub fn idx_buffer_u32(&self) -> Vec<u32> {
+        let mut indices: Vec<u32> = self.idx_buffer.iter().map(|&i| i as u32).collect();
+        for cmd in &self.commands {
+            if let FlattenedCmd::Elements { count, cmd_params } = cmd {
+                let end = (cmd_params.idx_offset + count).min(indices.len());
+                if let Some(range) = indices.get_mut(cmd_params.idx_offset..end) {
+                    for idx in range {
+                        *idx += cmd_params.vtx_offset as u32;
+                    }
+                }
+            }
+        }
+        indices
+    }
+}
+
+fn to_u8(v: f32) -> u8 {
+    (v * 255.0).round() as u8
+}
+
+fn srgb_to_linear(v: f32) -> f32 {
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(v: f32) -> f32 {
+    if v <= 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+#[test]
+fn test_flatten_rebases_offsets() {
+    use crate::render::renderer::TextureId;
+    use crate::render::snapshot::{DrawListSnapshot, FrameSnapshot, SnapshotCmd};
+
+    let vertex = |x: f32, col: u8| DrawVert {
+        pos: [x, 20.0],
+        uv: [0.0, 0.0],
+        col: [col, col, col, 128],
+    };
+    let params = |vtx_offset, idx_offset| DrawCmdParams {
+        clip_rect: [10.0, 20.0, 110.0, 120.0],
+        texture_id: TextureId::new(1),
+        vtx_offset,
+        idx_offset,
+    };
+    let elements = |count, vtx_offset, idx_offset| SnapshotCmd::Elements {
+        count,
+        cmd_params: params(vtx_offset, idx_offset),
+    };
+    let list = |col| DrawListSnapshot {
+        vtx_buffer: vec![vertex(10.0, col), vertex(20.0, col), vertex(30.0, col)],
+        idx_buffer: vec![0, 1, 2, 2, 1, 0],
+        commands: vec![elements(3, 0, 0), elements(3, 0, 3)],
+    };
+    let snapshot = FrameSnapshot {
+        display_pos: [10.0, 20.0],
+        display_size: [100.0, 100.0],
+        framebuffer_scale: [1.0, 1.0],
+        draw_lists: vec![list(0), list(255)],
+    };
+    let owned = snapshot.to_owned_draw_data();
+    let mut flat = owned.draw_data().unwrap().flatten();
+
+    assert_eq!(flat.vtx_buffer.len(), 6);
+    assert_eq!(flat.idx_buffer.len(), 12);
+    let offsets: Vec<_> = flat
+        .commands
+        .iter()
+        .map(|cmd| match cmd {
+            FlattenedCmd::Elements { count, cmd_params } => {
+                (*count, cmd_params.vtx_offset, cmd_params.idx_offset)
+            }
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(offsets, [(3, 0, 0), (3, 0, 3), (3, 3, 6), (3, 3, 9)]);
+    assert_eq!(flat.idx_buffer_u32(), [0, 1, 2, 2, 1, 0, 3, 4, 5, 5, 4, 3]);
+
+    flat.translate_to_origin();
+    assert_eq!(flat.display_pos, [0.0, 0.0]);
+    assert_eq!(flat.vtx_buffer[0].pos, [0.0, 0.0]);
+    match &flat.commands[0] {
+        FlattenedCmd::Elements { cmd_params, .. } => {
+            assert_eq!(cmd_params.clip_rect, [0.0, 0.0, 100.0, 100.0])
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_flatten_keeps_callbacks() {
+    use crate::render::draw_data::BlendMode;
+
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.new_frame();
+    {
+        let draw_list = ui.get_background_draw_list();
+        draw_list.set_blend_mode(BlendMode::Additive);
+        draw_list
+            .add_user_callback(|context| *context.downcast_mut::<u32>().unwrap() += 1)
+            .build();
+    }
+    let draw_data = ctx.render();
+
+    let mut blend_modes = Vec::new();
+    let mut context = 0u32;
+    for cmd in draw_data.flatten().commands {
+        if let FlattenedCmd::UserCallback { callback, .. } = cmd {
+            match callback {
+                DrawCallback::SetBlendMode(mode) => blend_modes.push(mode),
+                DrawCallback::SetSampler(_) => {}
+                DrawCallback::User(callback) => callback.invoke(&mut context),
+            }
+        }
+    }
+    assert_eq!(blend_modes, [BlendMode::Additive]);
+    assert_eq!(context, 1);
+}
+
+#[test]
+fn test_flatten_color_conversion() {
+    let mut flat = FlattenedDrawData {
+        vtx_buffer: vec![DrawVert {
+            pos: [0.0, 0.0],
+            uv: [0.0, 0.0],
+            col: [0, 128, 255, 128],
+        }],
+        ..Default::default()
+    };
+    flat.convert_colors_to_linear();
+    assert_eq!(flat.vtx_buffer[0].col, [0, 55, 255, 128]);
+    flat.convert_colors_to_srgb();
+    assert_eq!(flat.vtx_buffer[0].col, [0, 128, 255, 128]);
+}
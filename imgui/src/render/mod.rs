@@ -1,2 +1,4 @@
 pub mod draw_data;
+pub mod flatten;
 pub mod renderer;
+pub mod snapshot;
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use crate::render::draw_data::{DrawCmd, DrawData};
+
 /// An opaque texture identifier
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 #[repr(transparent)]
@@ -53,11 +55,65 @@ fn test_texture_id_memory_layout() {
     );
 }
 
+/// A rectangular region of a texture, in pixels.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct TextureRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl TextureRegion {
+    /// Creates a region from its upper-left corner and size.
+    #[inline]
+    pub const fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        TextureRegion {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TextureEntry<T> {
+    texture: T,
+    generation: u64,
+    dirty: Vec<TextureRegion>,
+    last_used_frame: Option<u64>,
+    pending_removal: bool,
+}
+
+impl<T> TextureEntry<T> {
+    fn new(texture: T) -> Self {
+        TextureEntry {
+            texture,
+            generation: 0,
+            dirty: Vec::new(),
+            last_used_frame: None,
+            pending_removal: false,
+        }
+    }
+}
+
 /// Generic texture mapping for use by renderers.
+///
+/// Besides mapping [`TextureId`]s to renderer textures, this keeps track of the state a renderer
+/// needs to manage texture lifetimes:
+///
+/// - a generation counter per texture, bumped whenever the texture is replaced or modified, so
+///   that cached bind groups or descriptor sets can be invalidated;
+/// - dirty regions, for uploading only the modified parts of a texture;
+/// - deferred removal, which keeps a texture alive until every frame that referenced it has
+///   finished rendering. Call [`Textures::track_frame`] for each submitted frame and
+///   [`Textures::frame_rendered`] once the GPU is done with it.
 #[derive(Debug)]
 pub struct Textures<T> {
-    textures: HashMap<usize, T>,
+    textures: HashMap<usize, TextureEntry<T>>,
     next: usize,
+    frame: u64,
 }
 
 /// We manually impl Default as `#[derive(Default)]`
@@ -68,6 +124,7 @@ impl<T> Default for Textures<T> {
         Self {
             textures: Default::default(),
             next: Default::default(),
+            frame: Default::default(),
         }
     }
 }
@@ -79,29 +136,236 @@ impl<T> Textures<T> {
         Textures {
             textures: HashMap::new(),
             next: 0,
+            frame: 0,
         }
     }
 
     pub fn insert(&mut self, texture: T) -> TextureId {
         let id = self.next;
-        self.textures.insert(id, texture);
+        self.textures.insert(id, TextureEntry::new(texture));
         self.next += 1;
         TextureId::from(id)
     }
 
+    /// Replaces the texture stored under `id`, returning the previous one.
+    ///
+    /// If there was a texture already, its generation is bumped and its dirty regions are
+    /// cleared, as the new texture is expected to be uploaded in full.
     pub fn replace(&mut self, id: TextureId, texture: T) -> Option<T> {
-        self.textures.insert(id.0, texture)
+        match self.textures.get_mut(&id.0) {
+            Some(entry) => {
+                entry.generation += 1;
+                entry.dirty.clear();
+                entry.pending_removal = false;
+                Some(std::mem::replace(&mut entry.texture, texture))
+            }
+            None => {
+                self.textures.insert(id.0, TextureEntry::new(texture));
+                None
+            }
+        }
     }
 
+    /// Removes a texture immediately.
+    ///
+    /// Use [`Textures::remove_deferred`] if the texture may still be used by frames in flight.
     pub fn remove(&mut self, id: TextureId) -> Option<T> {
-        self.textures.remove(&id.0)
+        self.textures.remove(&id.0).map(|entry| entry.texture)
     }
 
     pub fn get(&self, id: TextureId) -> Option<&T> {
-        self.textures.get(&id.0)
+        self.textures.get(&id.0).map(|entry| &entry.texture)
     }
 
     pub fn get_mut(&mut self, id: TextureId) -> Option<&mut T> {
-        self.textures.get_mut(&id.0)
+        self.textures.get_mut(&id.0).map(|entry| &mut entry.texture)
+    }
+
+    /// Returns the generation of a texture, which starts at 0 and is bumped every time the
+    /// texture is replaced or marked dirty.
+    pub fn generation(&self, id: TextureId) -> Option<u64> {
+        self.textures.get(&id.0).map(|entry| entry.generation)
+    }
+
+    /// Records that `region` of a texture was modified and has to be uploaded again.
+    ///
+    /// Returns `false` if there is no texture with that id.
+    pub fn mark_dirty(&mut self, id: TextureId, region: TextureRegion) -> bool {
+        match self.textures.get_mut(&id.0) {
+            Some(entry) => {
+                entry.generation += 1;
+                if !entry.dirty.contains(&region) {
+                    entry.dirty.push(region);
+                }
+                true
+            }
+            None => false,
+        }
     }
+
+    /// Returns `true` if the texture has modified regions which haven't been uploaded yet.
+    pub fn is_dirty(&self, id: TextureId) -> bool {
+        self.textures
+            .get(&id.0)
+            .is_some_and(|entry| !entry.dirty.is_empty())
+    }
+
+    /// Returns an iterator over the ids of all textures with pending dirty regions.
+    pub fn dirty_textures(&self) -> impl Iterator<Item = TextureId> + '_ {
+        self.textures
+            .iter()
+            .filter(|(_, entry)| !entry.dirty.is_empty())
+            .map(|(&id, _)| TextureId(id))
+    }
+
+    /// Takes the dirty regions of a texture, for the renderer to upload them.
+    pub fn take_dirty_regions(&mut self, id: TextureId) -> Vec<TextureRegion> {
+        self.textures
+            .get_mut(&id.0)
+            .map(|entry| std::mem::take(&mut entry.dirty))
+            .unwrap_or_default()
+    }
+
+    /// Returns an iterator over the textures referenced by `draw_data`, each id appearing once.
+    ///
+    /// Ids that aren't known to this map are skipped.
+    pub fn referenced_by<'a>(
+        &'a self,
+        draw_data: &'a DrawData,
+    ) -> impl Iterator<Item = (TextureId, &'a T)> + 'a {
+        referenced_texture_ids(draw_data)
+            .into_iter()
+            .filter_map(move |id| self.get(id).map(|texture| (id, texture)))
+    }
+
+    /// Records that a frame using `draw_data` is being submitted, and returns its frame number.
+    ///
+    /// Textures referenced by the frame won't be released by deferred removal until
+    /// [`Textures::frame_rendered`] is called with this frame number or a later one.
+    pub fn track_frame(&mut self, draw_data: &DrawData) -> u64 {
+        self.frame += 1;
+        for id in referenced_texture_ids(draw_data) {
+            if let Some(entry) = self.textures.get_mut(&id.0) {
+                entry.last_used_frame = Some(self.frame);
+            }
+        }
+        self.frame
+    }
+
+    /// Schedules a texture for removal once the last frame referencing it has been rendered.
+    ///
+    /// The texture remains accessible until then, so frames in flight can still use it. Returns
+    /// `false` if there is no texture with that id.
+    pub fn remove_deferred(&mut self, id: TextureId) -> bool {
+        match self.textures.get_mut(&id.0) {
+            Some(entry) => {
+                entry.pending_removal = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Notifies that every frame up to and including `frame` has finished rendering.
+    ///
+    /// Returns the textures scheduled with [`Textures::remove_deferred`] which are no longer used
+    /// by any frame in flight, so that the renderer can destroy them.
+    pub fn frame_rendered(&mut self, frame: u64) -> Vec<(TextureId, T)> {
+        let released: Vec<usize> = self
+            .textures
+            .iter()
+            .filter(|(_, entry)| {
+                entry.pending_removal && entry.last_used_frame.is_none_or(|used| used <= frame)
+            })
+            .map(|(&id, _)| id)
+            .collect();
+        released
+            .into_iter()
+            .filter_map(|id| {
+                self.textures
+                    .remove(&id)
+                    .map(|entry| (TextureId(id), entry.texture))
+            })
+            .collect()
+    }
+}
+
+fn referenced_texture_ids(draw_data: &DrawData) -> Vec<TextureId> {
+    let mut ids = Vec::new();
+    for draw_list in draw_data.draw_lists() {
+        for cmd in draw_list.commands() {
+            if let DrawCmd::Elements { cmd_params, .. } = cmd {
+                if !ids.contains(&cmd_params.texture_id) {
+                    ids.push(cmd_params.texture_id);
+                }
+            }
+        }
+    }
+    ids
+}
+
+#[test]
+fn test_textures_deferred_removal() {
+    use crate::render::draw_data::DrawCmdParams;
+    use crate::render::snapshot::{DrawListSnapshot, FrameSnapshot, SnapshotCmd};
+
+    let mut textures = Textures::new();
+    let a = textures.insert("a");
+    let b = textures.insert("b");
+
+    let frame = |id: TextureId| FrameSnapshot {
+        draw_lists: vec![DrawListSnapshot {
+            commands: vec![SnapshotCmd::Elements {
+                count: 0,
+                cmd_params: DrawCmdParams {
+                    clip_rect: [0.0; 4],
+                    texture_id: id,
+                    vtx_offset: 0,
+                    idx_offset: 0,
+                },
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let frame_a = frame(a).to_owned_draw_data();
+    let frame_a = frame_a.draw_data().unwrap();
+    assert_eq!(
+        textures.referenced_by(frame_a).collect::<Vec<_>>(),
+        [(a, &"a")]
+    );
+
+    let first = textures.track_frame(frame_a);
+    assert!(textures.remove_deferred(a));
+    assert!(textures.remove_deferred(b));
+    // `b` isn't used by any frame in flight
+    assert_eq!(textures.frame_rendered(first - 1), [(b, "b")]);
+    assert_eq!(textures.get(a), Some(&"a"));
+
+    let second = textures.track_frame(frame_a);
+    assert!(textures.frame_rendered(first).is_empty());
+    assert_eq!(textures.frame_rendered(second), [(a, "a")]);
+    assert_eq!(textures.get(a), None);
+}
+
+#[test]
+fn test_textures_generation_and_dirty_regions() {
+    let mut textures = Textures::new();
+    let id = textures.insert(1);
+    assert_eq!(textures.generation(id), Some(0));
+    assert!(!textures.is_dirty(id));
+
+    let region = TextureRegion::new(0, 0, 16, 16);
+    assert!(textures.mark_dirty(id, region));
+    assert!(textures.mark_dirty(id, region));
+    assert_eq!(textures.generation(id), Some(2));
+    assert_eq!(textures.dirty_textures().collect::<Vec<_>>(), [id]);
+    assert_eq!(textures.take_dirty_regions(id), [region]);
+    assert!(!textures.is_dirty(id));
+
+    textures.mark_dirty(id, region);
+    assert_eq!(textures.replace(id, 2), Some(1));
+    assert_eq!(textures.generation(id), Some(4));
+    assert!(!textures.is_dirty(id));
+    assert!(!textures.mark_dirty(TextureId::new(42), region));
 }
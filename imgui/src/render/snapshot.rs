@@ -0,0 +1,691 @@
+//! Owned, portable copies of a frame's draw data.
+//!
+//! A [`FrameSnapshot`] holds everything a renderer needs to draw a frame, without any pointers
+//! into the Dear ImGui context. It can be encoded to a compact binary format with
+//! [`FrameSnapshot::to_bytes`], sent to another process, decoded with
+//! [`FrameSnapshot::from_bytes`] and handed to an unmodified renderer through
+//! [`FrameSnapshot::to_owned_draw_data`].
+//!
+//! ```no_run
+//! # use imgui::*;
+//! # let mut ctx = Context::create();
+//! # let _ = ctx.new_frame();
+//! let snapshot = FrameSnapshot::from(ctx.render());
+//! let bytes = snapshot.to_bytes();
+//!
+//! // ... on the viewer side
+//! let snapshot = FrameSnapshot::from_bytes(&bytes).unwrap();
+//! let owned = snapshot.to_owned_draw_data();
+//! let draw_data = owned.draw_data().unwrap();
+//! ```
+
+use std::fmt;
+
+use crate::render::draw_data::{
+    typed_callback, BlendMode, DrawCallback, DrawCmd, DrawCmdParams, DrawData, DrawIdx, DrawList,
+    DrawVert, OwnedDrawData, SamplerFilter,
+};
+use crate::render::renderer::TextureId;
+use crate::sys;
+
+const MAGIC: [u8; 4] = *b"IMFS";
+const VERSION: u16 = 1;
+
+const CMD_ELEMENTS: u8 = 0;
+const CMD_RESET_RENDER_STATE: u8 = 1;
+const CMD_SET_BLEND_MODE: u8 = 2;
+const CMD_SET_SAMPLER: u8 = 3;
+
+/// An owned copy of all draw data of a single frame.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FrameSnapshot {
+    /// Upper-left position of the viewport to render.
+    pub display_pos: [f32; 2],
+    /// Size of the viewport to render.
+    pub display_size: [f32; 2],
+    /// Amount of pixels for each unit of display_size.
+    pub framebuffer_scale: [f32; 2],
+    /// The draw lists of the frame, in rendering order.
+    pub draw_lists: Vec<DrawListSnapshot>,
+}
+
+/// An owned copy of a single draw list.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DrawListSnapshot {
+    pub vtx_buffer: Vec<DrawVert>,
+    pub idx_buffer: Vec<DrawIdx>,
+    pub commands: Vec<SnapshotCmd>,
+}
+
+/// An owned draw command.
+///
+/// Raw and user callbacks run arbitrary code in the process that built the frame, so they can't
+/// be part of a snapshot and are dropped. The blend mode and sampler markers are kept.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SnapshotCmd {
+    Elements {
+        /// The number of indices used for this draw command
+        count: usize,
+        cmd_params: DrawCmdParams,
+    },
+    ResetRenderState,
+    SetBlendMode(BlendMode),
+    SetSampler(SamplerFilter),
+}
+
+impl FrameSnapshot {
+    /// Sum of all draw list vertex buffer sizes.
+    pub fn total_vtx_count(&self) -> usize {
+        self.draw_lists.iter().map(|l| l.vtx_buffer.len()).sum()
+    }
+
+    /// Sum of all draw list index buffer sizes.
+    pub fn total_idx_count(&self) -> usize {
+        self.draw_lists.iter().map(|l| l.idx_buffer.len()).sum()
+    }
+
+    /// Returns an iterator over the texture ids used by the frame, in order of first use.
+    pub fn texture_ids(&self) -> impl Iterator<Item = TextureId> + '_ {
+        let mut seen = Vec::new();
+        self.draw_lists
+            .iter()
+            .flat_map(|l| l.commands.iter())
+            .filter_map(move |cmd| match cmd {
+                SnapshotCmd::Elements { cmd_params, .. }
+                    if !seen.contains(&cmd_params.texture_id) =>
+                {
+                    seen.push(cmd_params.texture_id);
+                    Some(cmd_params.texture_id)
+                }
+                _ => None,
+            })
+    }
+
+    /// Encodes the snapshot in the binary frame format.
+    ///
+    /// All values are little-endian. The format is versioned, and
+    /// [`FrameSnapshot::from_bytes`] rejects data written by a different version.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(
+            32 + self.total_vtx_count() * VERTEX_SIZE
+                + self.total_idx_count() * size_of::<DrawIdx>(),
+        );
+        out.extend_from_slice(&MAGIC);
+        out.extend_from_slice(&VERSION.to_le_bytes());
+        out.push(size_of::<DrawIdx>() as u8);
+        for v in self
+            .display_pos
+            .iter()
+            .chain(&self.display_size)
+            .chain(&self.framebuffer_scale)
+        {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        put_len(&mut out, self.draw_lists.len());
+        for list in &self.draw_lists {
+            list.encode(&mut out);
+        }
+        out
+    }
+
+    /// Decodes a snapshot written by [`FrameSnapshot::to_bytes`].
+    ///
+    /// The decoded snapshot is checked with [`FrameSnapshot::validate`], so it is safe to pass to
+    /// a renderer.
+    pub fn from_bytes(bytes: &[u8]) -> Result<FrameSnapshot, SnapshotDecodeError> {
+        let snapshot = Self::from_bytes_unvalidated(bytes)?;
+        snapshot.validate()?;
+        Ok(snapshot)
+    }
+
+    /// Decodes a snapshot without checking its draw commands, for frames which are completed
+    /// after decoding.
+    pub(crate) fn from_bytes_unvalidated(
+        bytes: &[u8],
+    ) -> Result<FrameSnapshot, SnapshotDecodeError> {
+        let mut r = Reader { bytes };
+        if r.take(4)? != MAGIC {
+            return Err(SnapshotDecodeError::BadMagic);
+        }
+        let version = u16::from_le_bytes(r.array()?);
+        if version != VERSION {
+            return Err(SnapshotDecodeError::UnsupportedVersion(version));
+        }
+        let idx_size = r.u8()?;
+        if idx_size as usize != size_of::<DrawIdx>() {
+            return Err(SnapshotDecodeError::IndexSizeMismatch(idx_size));
+        }
+        let display_pos = [r.f32()?, r.f32()?];
+        let display_size = [r.f32()?, r.f32()?];
+        let framebuffer_scale = [r.f32()?, r.f32()?];
+        let list_count = r.len()?;
+        let mut draw_lists = Vec::with_capacity(list_count.min(r.bytes.len()));
+        for _ in 0..list_count {
+            draw_lists.push(DrawListSnapshot::decode(&mut r)?);
+        }
+        if !r.bytes.is_empty() {
+            return Err(SnapshotDecodeError::TrailingBytes(r.bytes.len()));
+        }
+        Ok(FrameSnapshot {
+            display_pos,
+            display_size,
+            framebuffer_scale,
+            draw_lists,
+        })
+    }
+
+    /// Checks that every draw command only uses indices and vertices inside its draw list.
+    ///
+    /// Renderers trust these ranges, so snapshots which weren't created from a [`DrawData`] or
+    /// decoded by [`FrameSnapshot::from_bytes`] should be checked before rendering them.
+    pub fn validate(&self) -> Result<(), SnapshotDecodeError> {
+        self.draw_lists
+            .iter()
+            .try_for_each(DrawListSnapshot::validate)
+    }
+
+    /// Rebuilds Dear ImGui draw data from the snapshot, so that it can be passed to any renderer.
+    ///
+    /// This doesn't need an active context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the snapshot is invalid, see [`FrameSnapshot::validate`].
+    pub fn to_owned_draw_data(&self) -> OwnedDrawData {
+        if let Err(err) = self.validate() {
+            panic!("Invalid frame snapshot: {}", err);
+        }
+        unsafe {
+            let draw_data = sys::ImDrawData_ImDrawData();
+            (*draw_data).Valid = true;
+            (*draw_data).TotalVtxCount = self.total_vtx_count() as i32;
+            (*draw_data).TotalIdxCount = self.total_idx_count() as i32;
+            (*draw_data).DisplayPos = self.display_pos.into();
+            (*draw_data).DisplaySize = self.display_size.into();
+            (*draw_data).FramebufferScale = self.framebuffer_scale.into();
+
+            let lists: Vec<*mut sys::ImDrawList> =
+                self.draw_lists.iter().map(|l| l.to_raw()).collect();
+            (*draw_data).CmdLists.Data = im_alloc_copy(&lists);
+            (*draw_data).CmdLists.Size = lists.len() as i32;
+            (*draw_data).CmdLists.Capacity = lists.len() as i32;
+            (*draw_data).CmdListsCount = lists.len() as i32;
+            OwnedDrawData::from_raw(draw_data)
+        }
+    }
+}
+
+impl From<&DrawData> for FrameSnapshot {
+    fn from(draw_data: &DrawData) -> Self {
+        FrameSnapshot {
+            display_pos: draw_data.display_pos,
+            display_size: draw_data.display_size,
+            framebuffer_scale: draw_data.framebuffer_scale,
+            draw_lists: draw_data.draw_lists().map(DrawListSnapshot::from).collect(),
+        }
+    }
+}
+
+impl From<&DrawList> for DrawListSnapshot {
+    fn from(draw_list: &DrawList) -> Self {
+        DrawListSnapshot {
+            vtx_buffer: draw_list.vtx_buffer().to_vec(),
+            idx_buffer: draw_list.idx_buffer().to_vec(),
+            commands: draw_list
+                .commands()
+                .filter_map(|cmd| match cmd {
+                    DrawCmd::Elements { count, cmd_params } => {
+                        Some(SnapshotCmd::Elements { count, cmd_params })
+                    }
+                    DrawCmd::ResetRenderState => Some(SnapshotCmd::ResetRenderState),
+                    DrawCmd::UserCallback {
+                        callback: DrawCallback::SetBlendMode(mode),
+                        ..
+                    } => Some(SnapshotCmd::SetBlendMode(mode)),
+                    DrawCmd::UserCallback {
+                        callback: DrawCallback::SetSampler(filter),
+                        ..
+                    } => Some(SnapshotCmd::SetSampler(filter)),
+                    DrawCmd::UserCallback { .. } | DrawCmd::RawCallback { .. } => None,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl DrawListSnapshot {
+    /// Checks the ranges of the draw commands, see [`FrameSnapshot::validate`].
+    pub fn validate(&self) -> Result<(), SnapshotDecodeError> {
+        for (i, cmd) in self.commands.iter().enumerate() {
+            let SnapshotCmd::Elements { count, cmd_params } = *cmd else {
+                continue;
+            };
+            let indices = cmd_params
+                .idx_offset
+                .checked_add(count)
+                .and_then(|end| self.idx_buffer.get(cmd_params.idx_offset..end))
+                .ok_or(SnapshotDecodeError::InvalidCommandRange(i))?;
+            let vertices = self
+                .vtx_buffer
+                .get(cmd_params.vtx_offset..)
+                .ok_or(SnapshotDecodeError::InvalidCommandRange(i))?;
+            if let Some(&idx) = indices.iter().find(|&&idx| idx as usize >= vertices.len()) {
+                return Err(SnapshotDecodeError::InvalidIndex(idx as usize));
+            }
+        }
+        Ok(())
+    }
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        put_len(out, self.vtx_buffer.len());
+        for vtx in &self.vtx_buffer {
+            put_vertex(out, vtx);
+        }
+        put_len(out, self.idx_buffer.len());
+        for idx in &self.idx_buffer {
+            out.extend_from_slice(&idx.to_le_bytes());
+        }
+        put_len(out, self.commands.len());
+        for cmd in &self.commands {
+            match *cmd {
+                SnapshotCmd::Elements { count, cmd_params } => {
+                    out.push(CMD_ELEMENTS);
+                    for v in &cmd_params.clip_rect {
+                        out.extend_from_slice(&v.to_le_bytes());
+                    }
+                    out.extend_from_slice(&(cmd_params.texture_id.id() as u64).to_le_bytes());
+                    put_len(out, cmd_params.vtx_offset);
+                    put_len(out, cmd_params.idx_offset);
+                    put_len(out, count);
+                }
+                SnapshotCmd::ResetRenderState => out.push(CMD_RESET_RENDER_STATE),
+                SnapshotCmd::SetBlendMode(mode) => {
+                    out.push(CMD_SET_BLEND_MODE);
+                    out.push(mode as u8);
+                }
+                SnapshotCmd::SetSampler(filter) => {
+                    out.push(CMD_SET_SAMPLER);
+                    out.push(filter as u8);
+                }
+            }
+        }
+    }
+
+    fn decode(r: &mut Reader<'_>) -> Result<DrawListSnapshot, SnapshotDecodeError> {
+        let vtx_count = r.len()?;
+        let mut vtx_buffer = Vec::with_capacity(vtx_count.min(r.bytes.len() / VERTEX_SIZE));
+        for _ in 0..vtx_count {
+            vtx_buffer.push(r.vertex()?);
+        }
+        let idx_count = r.len()?;
+        let mut idx_buffer = Vec::with_capacity(idx_count.min(r.bytes.len()));
+        for _ in 0..idx_count {
+            idx_buffer.push(DrawIdx::from_le_bytes(r.array()?));
+        }
+        let cmd_count = r.len()?;
+        let mut commands = Vec::with_capacity(cmd_count.min(r.bytes.len()));
+        for _ in 0..cmd_count {
+            let cmd = match r.u8()? {
+                CMD_ELEMENTS => {
+                    let clip_rect = [r.f32()?, r.f32()?, r.f32()?, r.f32()?];
+                    let texture_id = u64::from_le_bytes(r.array()?);
+                    let texture_id = usize::try_from(texture_id)
+                        .map_err(|_| SnapshotDecodeError::InvalidTextureId(texture_id))?;
+                    SnapshotCmd::Elements {
+                        cmd_params: DrawCmdParams {
+                            clip_rect,
+                            texture_id: TextureId::new(texture_id),
+                            vtx_offset: r.len()?,
+                            idx_offset: r.len()?,
+                        },
+                        count: r.len()?,
+                    }
+                }
+                CMD_RESET_RENDER_STATE => SnapshotCmd::ResetRenderState,
+                CMD_SET_BLEND_MODE => {
+                    let value = r.u8()?;
+                    SnapshotCmd::SetBlendMode(
+                        *BlendMode::VARIANTS
+                            .get(value as usize)
+                            .ok_or(SnapshotDecodeError::InvalidCommand(value))?,
+                    )
+                }
+                CMD_SET_SAMPLER => {
+                    let value = r.u8()?;
+                    SnapshotCmd::SetSampler(
+                        *SamplerFilter::VARIANTS
+                            .get(value as usize)
+                            .ok_or(SnapshotDecodeError::InvalidCommand(value))?,
+                    )
+                }
+                other => return Err(SnapshotDecodeError::InvalidCommand(other)),
+            };
+            commands.push(cmd);
+        }
+        Ok(DrawListSnapshot {
+            vtx_buffer,
+            idx_buffer,
+            commands,
+        })
+    }
+
+    unsafe fn to_raw(&self) -> *mut sys::ImDrawList {
+        let list = sys::ImDrawList_ImDrawList(std::ptr::null_mut());
+        let cmds: Vec<sys::ImDrawCmd> = self
+            .commands
+            .iter()
+            .map(|cmd| {
+                let mut raw = sys::ImDrawCmd {
+                    ClipRect: [0.0; 4].into(),
+                    TextureId: 0 as sys::ImTextureID,
+                    VtxOffset: 0,
+                    IdxOffset: 0,
+                    ElemCount: 0,
+                    UserCallback: Some(typed_callback),
+                    UserCallbackData: std::ptr::null_mut(),
+                };
+                match *cmd {
+                    SnapshotCmd::Elements { count, cmd_params } => {
+                        raw.ClipRect = cmd_params.clip_rect.into();
+                        raw.TextureId = cmd_params.texture_id.id() as sys::ImTextureID;
+                        raw.VtxOffset = cmd_params.vtx_offset as u32;
+                        raw.IdxOffset = cmd_params.idx_offset as u32;
+                        raw.ElemCount = count as u32;
+                        raw.UserCallback = None;
+                    }
+                    SnapshotCmd::ResetRenderState => {
+                        // ImDrawCallback_ResetRenderState
+                        raw.UserCallback = Some(std::mem::transmute::<
+                            isize,
+                            unsafe extern "C" fn(*const sys::ImDrawList, *const sys::ImDrawCmd),
+                        >(-1));
+                    }
+                    SnapshotCmd::SetBlendMode(mode) => {
+                        raw.UserCallbackData = DrawCallback::blend_mode_data(mode);
+                    }
+                    SnapshotCmd::SetSampler(filter) => {
+                        raw.UserCallbackData = DrawCallback::sampler_data(filter);
+                    }
+                }
+                raw
+            })
+            .collect();
+
+        (*list).CmdBuffer.Data = im_alloc_copy(&cmds);
+        (*list).CmdBuffer.Size = cmds.len() as i32;
+        (*list).CmdBuffer.Capacity = cmds.len() as i32;
+        (*list).IdxBuffer.Data = im_alloc_copy(&self.idx_buffer);
+        (*list).IdxBuffer.Size = self.idx_buffer.len() as i32;
+        (*list).IdxBuffer.Capacity = self.idx_buffer.len() as i32;
+        (*list).VtxBuffer.Data = im_alloc_copy(&self.vtx_buffer) as *mut sys::ImDrawVert;
+        (*list).VtxBuffer.Size = self.vtx_buffer.len() as i32;
+        (*list).VtxBuffer.Capacity = self.vtx_buffer.len() as i32;
+        list
+    }
+}
+
+/// Copies `items` into a buffer owned by Dear ImGui's allocator, so that it can be released by
+/// the destructors of the structures it is stored in.
+unsafe fn im_alloc_copy<T: Copy>(items: &[T]) -> *mut T {
+    if items.is_empty() {
+        return std::ptr::null_mut();
+    }
+    let ptr = sys::igMemAlloc(std::mem::size_of_val(items)) as *mut T;
+    std::ptr::copy_nonoverlapping(items.as_ptr(), ptr, items.len());
+    ptr
+}
+
+/// Size of an encoded [`DrawVert`], in bytes.
+pub(crate) const VERTEX_SIZE: usize = 20;
+
+pub(crate) fn put_len(out: &mut Vec<u8>, len: usize) {
+    out.extend_from_slice(&(len as u32).to_le_bytes());
+}
+
+pub(crate) fn put_vertex(out: &mut Vec<u8>, vtx: &DrawVert) {
+    for v in vtx.pos.iter().chain(&vtx.uv) {
+        out.extend_from_slice(&v.to_le_bytes());
+    }
+    out.extend_from_slice(&vtx.col);
+}
+
+/// Little-endian reader over an encoded frame.
+pub(crate) struct Reader<'a> {
+    pub(crate) bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn take(&mut self, n: usize) -> Result<&'a [u8], SnapshotDecodeError> {
+        if self.bytes.len() < n {
+            return Err(SnapshotDecodeError::UnexpectedEnd);
+        }
+        let (head, tail) = self.bytes.split_at(n);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    pub(crate) fn array<const N: usize>(&mut self) -> Result<[u8; N], SnapshotDecodeError> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8, SnapshotDecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(crate) fn f32(&mut self) -> Result<f32, SnapshotDecodeError> {
+        Ok(f32::from_le_bytes(self.array()?))
+    }
+
+    pub(crate) fn len(&mut self) -> Result<usize, SnapshotDecodeError> {
+        Ok(u32::from_le_bytes(self.array()?) as usize)
+    }
+
+    pub(crate) fn vertex(&mut self) -> Result<DrawVert, SnapshotDecodeError> {
+        Ok(DrawVert {
+            pos: [self.f32()?, self.f32()?],
+            uv: [self.f32()?, self.f32()?],
+            col: self.array()?,
+        })
+    }
+}
+
+/// The error returned when decoding a [`FrameSnapshot`] fails.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SnapshotDecodeError {
+    /// The data doesn't start with the frame format's magic bytes.
+    BadMagic,
+    /// The data was written by another version of the frame format.
+    UnsupportedVersion(u16),
+    /// The data was written with a different `DrawIdx` size, in bytes.
+    IndexSizeMismatch(u8),
+    /// The data ended in the middle of a frame.
+    UnexpectedEnd,
+    /// The data continues after the end of the frame.
+    TrailingBytes(usize),
+    /// A draw command has an unknown type or value.
+    InvalidCommand(u8),
+    /// A texture id doesn't fit in a `usize` on this platform.
+    InvalidTextureId(u64),
+    /// The draw command at this position in its draw list uses indices or vertices past the end
+    /// of the draw list's buffers.
+    InvalidCommandRange(usize),
+    /// A draw command uses this index, which is past the end of its vertices.
+    InvalidIndex(usize),
+}
+
+impl fmt::Display for SnapshotDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotDecodeError::BadMagic => f.pad("Not a frame snapshot"),
+            SnapshotDecodeError::UnsupportedVersion(v) => {
+                write!(f, "Unsupported frame snapshot version {}", v)
+            }
+            SnapshotDecodeError::IndexSizeMismatch(size) => write!(
+                f,
+                "Frame snapshot uses {}-byte indices -- expected {}",
+                size,
+                size_of::<DrawIdx>()
+            ),
+            SnapshotDecodeError::UnexpectedEnd => f.pad("Frame snapshot is truncated"),
+            SnapshotDecodeError::TrailingBytes(n) => {
+                write!(f, "{} unexpected bytes after frame snapshot", n)
+            }
+            SnapshotDecodeError::InvalidCommand(v) => {
+                write!(f, "Invalid draw command value {} in frame snapshot", v)
+            }
+            SnapshotDecodeError::InvalidTextureId(id) => {
+                write!(f, "Texture id {} does not fit in a usize", id)
+            }
+            SnapshotDecodeError::InvalidCommandRange(i) => {
+                write!(f, "Draw command {} is out of the draw list's bounds", i)
+            }
+            SnapshotDecodeError::InvalidIndex(idx) => {
+                write!(f, "Index {} is out of the draw command's vertices", idx)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SnapshotDecodeError {}
+
+#[cfg(test)]
+fn test_snapshot() -> FrameSnapshot {
+    FrameSnapshot {
+        display_pos: [1.0, 2.0],
+        display_size: [800.0, 600.0],
+        framebuffer_scale: [2.0, 2.0],
+        draw_lists: vec![DrawListSnapshot {
+            vtx_buffer: vec![
+                DrawVert {
+                    pos: [0.0, 0.0],
+                    uv: [0.5, 0.5],
+                    col: [255, 0, 0, 255],
+                },
+                DrawVert {
+                    pos: [10.0, 0.0],
+                    uv: [0.5, 0.5],
+                    col: [0, 255, 0, 255],
+                },
+                DrawVert {
+                    pos: [0.0, 10.0],
+                    uv: [0.5, 0.5],
+                    col: [0, 0, 255, 128],
+                },
+            ],
+            idx_buffer: vec![0, 1, 2],
+            commands: vec![
+                SnapshotCmd::SetBlendMode(BlendMode::Additive),
+                SnapshotCmd::Elements {
+                    count: 3,
+                    cmd_params: DrawCmdParams {
+                        clip_rect: [0.0, 0.0, 800.0, 600.0],
+                        texture_id: TextureId::new(7),
+                        vtx_offset: 0,
+                        idx_offset: 0,
+                    },
+                },
+                SnapshotCmd::ResetRenderState,
+                SnapshotCmd::SetSampler(SamplerFilter::Nearest),
+            ],
+        }],
+    }
+}
+
+#[test]
+fn test_snapshot_roundtrip_bytes() {
+    let snapshot = test_snapshot();
+    let bytes = snapshot.to_bytes();
+    assert_eq!(FrameSnapshot::from_bytes(&bytes), Ok(snapshot));
+
+    assert_eq!(
+        FrameSnapshot::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(SnapshotDecodeError::UnexpectedEnd)
+    );
+    assert_eq!(
+        FrameSnapshot::from_bytes(b"nope"),
+        Err(SnapshotDecodeError::BadMagic)
+    );
+}
+
+#[test]
+fn test_snapshot_rejects_out_of_bounds() {
+    let decode = |edit: fn(&mut DrawListSnapshot)| {
+        let mut snapshot = test_snapshot();
+        edit(&mut snapshot.draw_lists[0]);
+        FrameSnapshot::from_bytes(&snapshot.to_bytes())
+    };
+    fn elements(list: &mut DrawListSnapshot) -> (&mut usize, &mut DrawCmdParams) {
+        match &mut list.commands[1] {
+            SnapshotCmd::Elements { count, cmd_params } => (count, cmd_params),
+            _ => unreachable!(),
+        }
+    }
+    assert_eq!(
+        decode(|list| *elements(list).0 = 4),
+        Err(SnapshotDecodeError::InvalidCommandRange(1))
+    );
+    assert_eq!(
+        decode(|list| elements(list).1.idx_offset = 1),
+        Err(SnapshotDecodeError::InvalidCommandRange(1))
+    );
+    assert_eq!(
+        decode(|list| elements(list).1.vtx_offset = 4),
+        Err(SnapshotDecodeError::InvalidCommandRange(1))
+    );
+    assert_eq!(
+        decode(|list| elements(list).1.vtx_offset = 1),
+        Err(SnapshotDecodeError::InvalidIndex(2))
+    );
+    assert_eq!(
+        decode(|list| list.idx_buffer[2] = 3),
+        Err(SnapshotDecodeError::InvalidIndex(3))
+    );
+    // Indices outside of any command aren't used.
+    assert!(decode(|list| list.idx_buffer.push(100)).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "Invalid frame snapshot")]
+fn test_snapshot_draw_data_rejects_out_of_bounds() {
+    let mut snapshot = test_snapshot();
+    snapshot.draw_lists[0].vtx_buffer.pop();
+    snapshot.to_owned_draw_data();
+}
+
+#[test]
+fn test_snapshot_roundtrip_draw_data() {
+    let snapshot = test_snapshot();
+    let owned = snapshot.to_owned_draw_data();
+    let draw_data = owned.draw_data().unwrap();
+    assert_eq!(draw_data.draw_lists_count(), 1);
+    assert_eq!(draw_data.total_vtx_count, 3);
+    assert_eq!(FrameSnapshot::from(draw_data), snapshot);
+    assert_eq!(
+        snapshot.texture_ids().collect::<Vec<_>>(),
+        [TextureId::new(7)]
+    );
+}
+
+#[test]
+fn test_snapshot_from_frame() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.new_frame();
+    ui.get_background_draw_list()
+        .add_rect([10.0, 10.0], [20.0, 20.0], [1.0, 1.0, 1.0, 1.0])
+        .filled(true)
+        .build();
+    let draw_data = ctx.render();
+    let snapshot = FrameSnapshot::from(draw_data);
+    assert_eq!(snapshot.display_size, [1024.0, 768.0]);
+    assert_eq!(
+        snapshot.total_vtx_count(),
+        draw_data.total_vtx_count as usize
+    );
+    assert_eq!(
+        snapshot.total_idx_count(),
+        draw_data.total_idx_count as usize
+    );
+
+    let decoded = FrameSnapshot::from_bytes(&snapshot.to_bytes()).unwrap();
+    assert_eq!(decoded, snapshot);
+}
@@ -0,0 +1,371 @@
+use crate::math::MintVec2;
+use crate::Ui;
+
+/// Alignment of the items of a [`Stack`] across its direction: vertically in a horizontal
+/// stack, and horizontally in a vertical stack.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StackAlign {
+    /// Aligns items to the top or the left.
+    #[default]
+    Start,
+    /// Centers items.
+    Center,
+    /// Aligns items to the bottom or the right.
+    End,
+}
+
+/// Distribution of the items of a [`Stack`] along its direction, when they don't fill it.
+///
+/// Stacks with [`StackLayout::fill`] items are always filled, so this has no effect on them.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StackJustify {
+    /// Packs items at the start, separated by the spacing.
+    #[default]
+    Start,
+    /// Packs items in the middle, separated by the spacing.
+    Center,
+    /// Packs items at the end, separated by the spacing, for example to right-align buttons.
+    End,
+    /// Puts the first and last items at the ends, and spreads the others evenly between them.
+    SpaceBetween,
+    /// Spreads the items so the space before, between and after them is equal.
+    SpaceEvenly,
+}
+
+impl Ui {
+    /// Creates an unbuilt stack laying out items from left to right, see [`Stack`].
+    pub fn hstack<Label: AsRef<str>>(&self, label: Label) -> Stack<'_, Label> {
+        Stack::new(self, label, 0)
+    }
+
+    /// Creates an unbuilt stack laying out items from top to bottom, see [`Stack`].
+    pub fn vstack<Label: AsRef<str>>(&self, label: Label) -> Stack<'_, Label> {
+        Stack::new(self, label, 1)
+    }
+}
+
+/// Builder for a horizontal or vertical stack of items, which can be aligned, distributed, or
+/// sized to fill the stack.
+///
+/// Items are measured as they are built, and their sizes are kept under the id of the label to
+/// lay out the next frame. As a result, items which appear or change size are placed with the
+/// sizes of the previous frame for one frame.
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let mut query = String::new();
+/// // A toolbar with a search field taking the space left by the buttons.
+/// ui.hstack("toolbar")
+///     .align(StackAlign::Center)
+///     .build(|stack| {
+///         stack.item(|| ui.button("Open"));
+///         stack.fill(|_| ui.input_text("##search", &mut query).build());
+///         stack.fixed(80.0, || ui.button_with_size("Search", [80.0, 0.0]));
+///     });
+/// // Dialog buttons aligned to the right.
+/// ui.hstack("buttons")
+///     .justify(StackJustify::End)
+///     .build(|stack| {
+///         stack.item(|| ui.button("Cancel"));
+///         stack.item(|| ui.button("OK"));
+///     });
+/// ```
+#[derive(Copy, Clone, Debug)]
+#[must_use]
+pub struct Stack<'ui, Label> {
+    label: Label,
+    /// 0 for horizontal stacks, 1 for vertical stacks.
+    axis: usize,
+    spacing: Option<f32>,
+    align: StackAlign,
+    justify: StackJustify,
+    size: [f32; 2],
+    ui: &'ui Ui,
+}
+
+impl<'ui, Label: AsRef<str>> Stack<'ui, Label> {
+    fn new(ui: &'ui Ui, label: Label, axis: usize) -> Self {
+        Stack {
+            label,
+            axis,
+            spacing: None,
+            align: StackAlign::Start,
+            justify: StackJustify::Start,
+            size: [0.0, 0.0],
+            ui,
+        }
+    }
+
+    /// Sets the space between items.
+    ///
+    /// Defaults to the item spacing of the style along the stack.
+    #[inline]
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        self.spacing = Some(spacing);
+        self
+    }
+    /// Sets the alignment of items across the stack.
+    ///
+    /// Defaults to [`StackAlign::Start`].
+    #[inline]
+    pub fn align(mut self, align: StackAlign) -> Self {
+        self.align = align;
+        self
+    }
+    /// Sets the distribution of items along the stack.
+    ///
+    /// Defaults to [`StackJustify::Start`].
+    #[inline]
+    pub fn justify(mut self, justify: StackJustify) -> Self {
+        self.justify = justify;
+        self
+    }
+    /// Sets the size of the stack.
+    ///
+    /// Zero values use the available content region along the stack, and the size of the
+    /// largest item across it. Defaults to `[0.0, 0.0]`.
+    #[inline]
+    pub fn size(mut self, size: impl Into<MintVec2>) -> Self {
+        self.size = size.into().into();
+        self
+    }
+    /// Builds the stack, running `f` to add its items to the given [`StackLayout`].
+    pub fn build<R>(self, f: impl FnOnce(&mut StackLayout<'ui>) -> R) -> R {
+        let ui = self.ui;
+        let axis = self.axis;
+        let id = ui.new_id_str(self.label.as_ref());
+        let previous = ui.with_item_state(id, |items: &mut Vec<MeasuredItem>| items.clone());
+        let spacing = self
+            .spacing
+            .unwrap_or_else(|| ui.clone_style().item_spacing[axis]);
+
+        let length = match self.size[axis] {
+            size if size > 0.0 => size,
+            _ => ui.content_region_avail()[axis],
+        };
+        let cross_length = match self.size[1 - axis] {
+            size if size > 0.0 => size,
+            _ => previous
+                .iter()
+                .map(|item| item.size[1 - axis])
+                .fold(0.0, f32::max),
+        };
+
+        // Lay out this frame with the items measured in the previous frame.
+        let count = previous.len();
+        let fills = previous
+            .iter()
+            .filter(|item| item.sizing == Sizing::Fill)
+            .count();
+        let used: f32 = previous.iter().map(|item| item.length(axis)).sum();
+        let free = (length - used - spacing * count.saturating_sub(1) as f32).max(0.0);
+        let (lead, gap) = if fills > 0 {
+            (0.0, spacing)
+        } else {
+            match self.justify {
+                StackJustify::Start => (0.0, spacing),
+                StackJustify::Center => (free / 2.0, spacing),
+                StackJustify::End => (free, spacing),
+                StackJustify::SpaceBetween if count > 1 => {
+                    (0.0, spacing + free / (count - 1) as f32)
+                }
+                StackJustify::SpaceBetween => (0.0, spacing),
+                StackJustify::SpaceEvenly => {
+                    let space = free / (count + 1) as f32;
+                    (space, spacing + space)
+                }
+            }
+        };
+
+        let _group = ui.begin_group();
+        let mut layout = StackLayout {
+            ui,
+            axis,
+            align: self.align,
+            cross_length,
+            fill_length: if fills > 0 { free / fills as f32 } else { 0.0 },
+            gap,
+            next_pos: {
+                let mut pos = ui.cursor_screen_pos();
+                pos[axis] += lead;
+                pos
+            },
+            previous,
+            measured: Vec::new(),
+        };
+        let result = f(&mut layout);
+        let measured = layout.measured;
+        ui.with_item_state(id, |items: &mut Vec<MeasuredItem>| *items = measured);
+        result
+    }
+}
+
+/// How an item of a stack is sized along the stack.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Sizing {
+    Auto,
+    Fixed(f32),
+    Fill,
+}
+
+/// An item of a stack, as built in the last frame.
+#[derive(Copy, Clone, Debug)]
+struct MeasuredItem {
+    sizing: Sizing,
+    size: [f32; 2],
+    /// Width set with `set_next_item_width`, to find how much wider the item is with its label.
+    item_width: Option<f32>,
+}
+
+impl MeasuredItem {
+    /// Returns the length the item takes along the stack before fill items are sized.
+    fn length(&self, axis: usize) -> f32 {
+        match self.sizing {
+            Sizing::Auto => self.size[axis],
+            Sizing::Fixed(length) => length,
+            Sizing::Fill => 0.0,
+        }
+    }
+}
+
+/// Adds items to a [`Stack`], in order.
+///
+/// Each item is built by a closure, and should submit a single widget or a group of widgets,
+/// which are placed as one item.
+#[derive(Debug)]
+pub struct StackLayout<'ui> {
+    ui: &'ui Ui,
+    axis: usize,
+    align: StackAlign,
+    cross_length: f32,
+    fill_length: f32,
+    gap: f32,
+    next_pos: [f32; 2],
+    previous: Vec<MeasuredItem>,
+    measured: Vec<MeasuredItem>,
+}
+
+impl StackLayout<'_> {
+    /// Adds an item taking its own size.
+    pub fn item<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        self.add(Sizing::Auto, |_| f())
+    }
+
+    /// Adds an item taking `length` along the stack. In horizontal stacks, the width of the
+    /// next widget is set so the widget and its label fit in it.
+    pub fn fixed<R>(&mut self, length: f32, f: impl FnOnce() -> R) -> R {
+        self.add(Sizing::Fixed(length), |_| f())
+    }
+
+    /// Adds an item sharing the space left by the other items with the other fill items. `f`
+    /// receives the length given to the item along the stack.
+    ///
+    /// In horizontal stacks, the width of the next widget is set so the widget and its label
+    /// fit in it.
+    pub fn fill<R>(&mut self, f: impl FnOnce(f32) -> R) -> R {
+        self.add(Sizing::Fill, f)
+    }
+
+    fn add<R>(&mut self, sizing: Sizing, f: impl FnOnce(f32) -> R) -> R {
+        let ui = self.ui;
+        let (axis, cross) = (self.axis, 1 - self.axis);
+        let previous = self.previous.get(self.measured.len()).copied();
+        let length = match sizing {
+            Sizing::Auto => None,
+            Sizing::Fixed(length) => Some(length),
+            Sizing::Fill => Some(self.fill_length),
+        };
+
+        let mut pos = self.next_pos;
+        let previous_cross = previous.map_or(0.0, |item| item.size[cross]);
+        let factor = match self.align {
+            StackAlign::Start => 0.0,
+            StackAlign::Center => 0.5,
+            StackAlign::End => 1.0,
+        };
+        pos[cross] += ((self.cross_length - previous_cross) * factor).max(0.0);
+        ui.set_cursor_screen_pos(pos);
+
+        // Labels and other decorations measured in the last frame are taken out of the width.
+        let item_width = length.filter(|_| axis == 0).map(|length| {
+            let decoration = previous
+                .and_then(|item| Some(item.size[0] - item.item_width?))
+                .unwrap_or(0.0);
+            (length - decoration.max(0.0)).max(1.0)
+        });
+        let group = ui.begin_group();
+        if let Some(item_width) = item_width {
+            ui.set_next_item_width(item_width);
+        }
+        let result = f(length.unwrap_or(0.0));
+        group.end();
+
+        let size = ui.item_rect_size();
+        self.measured.push(MeasuredItem {
+            sizing,
+            size,
+            item_width,
+        });
+        self.next_pos[axis] += length.unwrap_or(size[axis]) + self.gap;
+        result
+    }
+}
+
+#[test]
+fn test_stack_layout() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let mut query = String::new();
+    for frame in 0..3 {
+        let ui = ctx.new_frame();
+        ui.window("stacks")
+            .position([0.0, 0.0], crate::Condition::Always)
+            .size([400.0, 300.0], crate::Condition::Always)
+            .no_decoration()
+            .build(|| {
+                // Window padding is 8, so the content region ends at 392.
+                let right = ui
+                    .hstack("right")
+                    .justify(StackJustify::End)
+                    .build(|stack| {
+                        stack.item(|| ui.button("Cancel"));
+                        stack.item(|| {
+                            ui.button("OK");
+                            ui.item_rect_max()[0]
+                        })
+                    });
+                let (search_right, button_left) = ui.hstack("fill").build(|stack| {
+                    stack.item(|| ui.button("Open"));
+                    let search_right = stack.fill(|_| {
+                        ui.input_text("Search", &mut query).build();
+                        ui.item_rect_max()[0]
+                    });
+                    let button_left = stack.item(|| {
+                        ui.button("Go");
+                        ui.item_rect_min()[0]
+                    });
+                    (search_right, button_left)
+                });
+                let centers = ui
+                    .vstack("centered")
+                    .align(StackAlign::Center)
+                    .build(|stack| {
+                        let mut center = |label| {
+                            stack.item(|| {
+                                ui.button(label);
+                                (ui.item_rect_min()[0] + ui.item_rect_max()[0]) / 2.0
+                            })
+                        };
+                        [center("A"), center("Longer")]
+                    });
+                if frame > 0 {
+                    assert_eq!(right, 392.0);
+                    // The search field and its label end one spacing before the last button.
+                    assert_eq!(search_right + 8.0, button_left);
+                    assert_eq!(centers[0], centers[1]);
+                }
+            });
+        ctx.render();
+    }
+}
@@ -0,0 +1,218 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use crate::sys;
+use crate::{Id, Ui};
+
+/// Frames an item state is kept for after its id was last used, see
+/// [`Ui::set_item_state_gc_frames`].
+const DEFAULT_ITEM_STATE_GC_FRAMES: i32 = 120;
+
+/// Key-value storage of the current window, as returned by [`Ui::state_storage`].
+///
+/// Dear ImGui uses this storage itself, for example to remember which tree nodes are open, so
+/// keys should be ids created with the [`Ui::new_id`] family of functions.
+#[derive(Debug)]
+pub struct Storage<'ui> {
+    raw: *mut sys::ImGuiStorage,
+    _phantom: PhantomData<&'ui Ui>,
+}
+
+impl Storage<'_> {
+    /// Returns the integer stored at `id`, or `default` if there is none.
+    #[doc(alias = "GetInt")]
+    pub fn get_int(&self, id: Id, default: i32) -> i32 {
+        unsafe { sys::ImGuiStorage_GetInt(self.raw, id.0, default) }
+    }
+
+    /// Stores an integer at `id`.
+    #[doc(alias = "SetInt")]
+    pub fn set_int(&mut self, id: Id, value: i32) {
+        unsafe { sys::ImGuiStorage_SetInt(self.raw, id.0, value) }
+    }
+
+    /// Returns the float stored at `id`, or `default` if there is none.
+    #[doc(alias = "GetFloat")]
+    pub fn get_float(&self, id: Id, default: f32) -> f32 {
+        unsafe { sys::ImGuiStorage_GetFloat(self.raw, id.0, default) }
+    }
+
+    /// Stores a float at `id`.
+    #[doc(alias = "SetFloat")]
+    pub fn set_float(&mut self, id: Id, value: f32) {
+        unsafe { sys::ImGuiStorage_SetFloat(self.raw, id.0, value) }
+    }
+
+    /// Returns the bool stored at `id`, or `default` if there is none.
+    #[doc(alias = "GetBool")]
+    pub fn get_bool(&self, id: Id, default: bool) -> bool {
+        unsafe { sys::ImGuiStorage_GetBool(self.raw, id.0, default) }
+    }
+
+    /// Stores a bool at `id`.
+    #[doc(alias = "SetBool")]
+    pub fn set_bool(&mut self, id: Id, value: bool) {
+        unsafe { sys::ImGuiStorage_SetBool(self.raw, id.0, value) }
+    }
+}
+
+struct ItemState {
+    value: Box<dyn Any>,
+    last_frame: i32,
+}
+
+struct ItemStates {
+    gc_frames: i32,
+    last_gc_frame: i32,
+    states: HashMap<(Id, TypeId), ItemState>,
+}
+
+impl Default for ItemStates {
+    fn default() -> Self {
+        ItemStates {
+            gc_frames: DEFAULT_ITEM_STATE_GC_FRAMES,
+            last_gc_frame: 0,
+            states: HashMap::new(),
+        }
+    }
+}
+
+impl ItemStates {
+    fn collect_garbage(&mut self, frame: i32) {
+        if frame == self.last_gc_frame {
+            return;
+        }
+        self.last_gc_frame = frame;
+        let gc_frames = self.gc_frames;
+        self.states
+            .retain(|_, state| frame.wrapping_sub(state.last_frame) <= gc_frames);
+    }
+}
+
+thread_local! {
+    // Keyed by the address of the raw Dear ImGui context, cleared when the context is dropped.
+    static ITEM_STATES: RefCell<HashMap<usize, ItemStates>> = RefCell::new(HashMap::new());
+}
+
+fn with_item_states<R>(f: impl FnOnce(&mut ItemStates) -> R) -> R {
+    let ctx = unsafe { sys::igGetCurrentContext() } as usize;
+    ITEM_STATES.with(|contexts| f(contexts.borrow_mut().entry(ctx).or_default()))
+}
+
+/// Drops the item states of a context that is being destroyed.
+pub(crate) fn clear_item_states(ctx: *mut sys::ImGuiContext) {
+    // Ignore failure, as thread locals may already be gone at thread exit.
+    let _ = ITEM_STATES.try_with(|contexts| contexts.borrow_mut().remove(&(ctx as usize)));
+}
+
+/// Implements [`Ui::with_item_state`], for code that has no `Ui` at hand such as token drops.
+pub(crate) fn with_item_state<T: Default + 'static, R>(id: Id, f: impl FnOnce(&mut T) -> R) -> R {
+    let frame = unsafe { sys::igGetFrameCount() };
+    let key = (id, TypeId::of::<T>());
+    // The state is taken out while `f` runs, so `f` can use the item states of other ids.
+    let mut value: Box<dyn Any> = with_item_states(|states| {
+        states.collect_garbage(frame);
+        states.states.remove(&key)
+    })
+    .map(|state| state.value)
+    .unwrap_or_else(|| Box::new(T::default()));
+    let result = f(value.downcast_mut().unwrap());
+    with_item_states(|states| {
+        states.states.insert(
+            key,
+            ItemState {
+                value,
+                last_frame: frame,
+            },
+        )
+    });
+    result
+}
+
+/// # Persistent state
+impl Ui {
+    /// Returns the key-value storage of the current window.
+    #[doc(alias = "GetStateStorage")]
+    pub fn state_storage(&self) -> Storage<'_> {
+        Storage {
+            raw: unsafe { sys::igGetStateStorage() },
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Runs `f` with the state of type `T` belonging to `id`, creating it with `T::default()` if
+    /// it doesn't exist yet.
+    ///
+    /// Unlike [`Storage`], the state can be any Rust type. It is dropped once `id` hasn't been
+    /// used with this function for a while (see [`Ui::set_item_state_gc_frames`]).
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// # let mut ctx = Context::create();
+    /// # let ui = ctx.frame();
+    /// let id = ui.new_id_str("fade");
+    /// let alpha = ui.with_item_state(id, |alpha: &mut f32| {
+    ///     *alpha = (*alpha + ui.io().delta_time).min(1.0);
+    ///     *alpha
+    /// });
+    /// ```
+    pub fn with_item_state<T: Default + 'static, R>(
+        &self,
+        id: Id,
+        f: impl FnOnce(&mut T) -> R,
+    ) -> R {
+        with_item_state(id, f)
+    }
+
+    /// Sets after how many frames without a call to [`Ui::with_item_state`] the state of an id
+    /// is dropped. Defaults to 120.
+    pub fn set_item_state_gc_frames(&self, frames: u32) {
+        let frames = frames.min(i32::MAX as u32) as i32;
+        with_item_states(|states| states.gc_frames = frames);
+    }
+}
+
+#[test]
+fn test_state_storage() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.frame();
+    let id = ui.new_id_str("state");
+    let mut storage = ui.state_storage();
+    assert_eq!(storage.get_int(id, 7), 7);
+    storage.set_int(id, 3);
+    assert_eq!(storage.get_int(id, 7), 3);
+    storage.set_float(id, 0.5);
+    assert_eq!(storage.get_float(id, 0.0), 0.5);
+    storage.set_bool(id, true);
+    assert!(storage.get_bool(id, false));
+}
+
+#[test]
+fn test_item_state_gc() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let kept = Id(1);
+    let dropped = Id(2);
+    for frame in 0..5 {
+        let ui = ctx.new_frame();
+        ui.set_item_state_gc_frames(2);
+        let count = ui.with_item_state(kept, |count: &mut u32| {
+            *count += 1;
+            *count
+        });
+        assert_eq!(count, frame + 1);
+        if frame == 0 {
+            ui.with_item_state(dropped, |name: &mut String| name.push('a'));
+        }
+        ctx.render();
+    }
+    let ui = ctx.new_frame();
+    assert_eq!(
+        ui.with_item_state(dropped, |name: &mut String| name.len()),
+        0
+    );
+    // Same id, different type.
+    assert_eq!(ui.with_item_state(kept, |count: &mut u64| *count), 0);
+    assert_eq!(ui.with_item_state(kept, |count: &mut u32| *count), 5);
+}
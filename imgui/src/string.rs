@@ -5,6 +5,8 @@ use std::os::raw::c_char;
 use std::str;
 use std::{fmt, ptr};
 
+use crate::sys;
+
 /// this is the unsafe cell upon which we build our abstraction.
 #[derive(Debug)]
 pub struct UiBuffer {
@@ -464,6 +466,138 @@ impl ToOwned for ImStr {
     }
 }
 
+/// A growable, nul-terminated text buffer allocated by Dear ImGui (`ImGuiTextBuffer`).
+///
+/// Text is appended with [`TextBuffer::append`] or through [`fmt::Write`], and the buffer can be
+/// handed to C++ APIs expecting an `ImGuiTextBuffer` without copying.
+#[repr(transparent)]
+pub struct TextBuffer(sys::ImGuiTextBuffer);
+
+impl TextBuffer {
+    /// Creates an empty buffer. Nothing is allocated until text is appended.
+    #[inline]
+    pub fn new() -> TextBuffer {
+        TextBuffer(sys::ImGuiTextBuffer::default())
+    }
+
+    /// Appends a string slice to the end of the buffer.
+    pub fn append(&mut self, text: &str) {
+        self.append_bytes(text.as_bytes());
+    }
+
+    /// Appends raw bytes, which may come from Dear ImGui and aren't guaranteed to be UTF-8.
+    pub(crate) fn append_bytes(&mut self, bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
+        }
+        let start = bytes.as_ptr() as *const c_char;
+        unsafe { sys::ImGuiTextBuffer_append(&mut self.0, start, start.add(bytes.len())) }
+    }
+
+    /// Returns the length of the text in bytes, excluding the nul terminator.
+    #[doc(alias = "size")]
+    pub fn len(&self) -> usize {
+        // `Buf` is either empty or holds the text followed by a nul terminator
+        (self.0.Buf.Size as usize).saturating_sub(1)
+    }
+
+    /// Returns true if the buffer contains no text.
+    #[doc(alias = "empty")]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all text and frees the allocation.
+    pub fn clear(&mut self) {
+        unsafe { sys::ImGuiTextBuffer_clear(&mut self.0) }
+    }
+
+    /// Reserves capacity for at least `additional` more bytes of text.
+    pub fn reserve(&mut self, additional: usize) {
+        let capacity = self.0.Buf.Size as usize + additional + 1;
+        unsafe { sys::ImGuiTextBuffer_reserve(&mut self.0, capacity.min(i32::MAX as usize) as i32) }
+    }
+
+    /// Returns the text as bytes, excluding the nul terminator.
+    pub fn as_bytes(&self) -> &[u8] {
+        if self.is_empty() {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(self.0.Buf.Data as *const u8, self.len()) }
+        }
+    }
+
+    /// Returns the text as a string slice, or an error if it isn't valid UTF-8 (which can only
+    /// happen if Dear ImGui wrote into the buffer).
+    pub fn to_str(&self) -> Result<&str, str::Utf8Error> {
+        str::from_utf8(self.as_bytes())
+    }
+
+    /// Returns the text as a string slice.
+    ///
+    /// Panics if the text isn't valid UTF-8, see [`to_str`](Self::to_str).
+    pub fn as_str(&self) -> &str {
+        self.to_str().expect("TextBuffer contains invalid UTF-8")
+    }
+
+    /// Returns the text as a C string, which ends at the first interior nul byte.
+    #[doc(alias = "c_str")]
+    pub fn as_c_str(&self) -> &CStr {
+        unsafe { CStr::from_ptr(sys::ImGuiTextBuffer_c_str(&self.0 as *const _ as *mut _)) }
+    }
+
+    /// Returns a reference to the underlying `ImGuiTextBuffer`.
+    pub fn raw(&self) -> &sys::ImGuiTextBuffer {
+        &self.0
+    }
+
+    /// Returns a mutable reference to the underlying `ImGuiTextBuffer`.
+    ///
+    /// # Safety
+    ///
+    /// The buffer must stay either empty or nul-terminated, and its memory must be allocated
+    /// with Dear ImGui's allocator.
+    pub unsafe fn raw_mut(&mut self) -> &mut sys::ImGuiTextBuffer {
+        &mut self.0
+    }
+}
+
+impl Default for TextBuffer {
+    fn default() -> Self {
+        TextBuffer::new()
+    }
+}
+
+impl Clone for TextBuffer {
+    fn clone(&self) -> Self {
+        let mut buffer = TextBuffer::new();
+        buffer.append_bytes(self.as_bytes());
+        buffer
+    }
+}
+
+impl Drop for TextBuffer {
+    fn drop(&mut self) {
+        if !self.0.Buf.Data.is_null() {
+            unsafe { sys::igMemFree(self.0.Buf.Data as *mut _) };
+        }
+    }
+}
+
+impl fmt::Debug for TextBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&String::from_utf8_lossy(self.as_bytes()), f)
+    }
+}
+
+impl fmt::Write for TextBuffer {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.append(s);
+        Ok(())
+    }
+}
+
 #[test]
 fn test_imstring_constructors() {
     let s = ImString::new("test");
@@ -529,3 +663,23 @@ fn test_imstring_interior_nul() {
     assert_eq!(s.to_str(), "");
     assert!(s.is_empty());
 }
+
+#[test]
+fn test_text_buffer() {
+    use std::fmt::Write;
+    let mut buf = TextBuffer::new();
+    assert!(buf.is_empty());
+    assert_eq!(buf.to_str(), Ok(""));
+    assert_eq!(buf.as_c_str(), c"");
+    buf.reserve(64);
+    buf.append("imgui-rs ");
+    let _ = write!(buf, "{:02x}", 0x42);
+    assert_eq!(buf.len(), 11);
+    assert_eq!(buf.to_str(), Ok("imgui-rs 42"));
+    assert_eq!(buf.as_c_str(), c"imgui-rs 42");
+    let copy = buf.clone();
+    buf.clear();
+    assert!(buf.is_empty());
+    assert_eq!(copy.to_str(), Ok("imgui-rs 42"));
+    assert_eq!(copy.as_str(), "imgui-rs 42");
+}
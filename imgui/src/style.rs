@@ -323,6 +323,9 @@ impl IndexMut<StyleColor> for Style {
     }
 }
 
+/// Color of validation messages, and of the borders of invalid inputs.
+pub(crate) const ERROR_COLOR: [f32; 4] = [1.0, 0.4, 0.4, 1.0];
+
 /// A color identifier for styling.
 ///
 /// Which color does what can sometimes be be unobvious. A good way to find a particular color is to use
@@ -1,5 +1,9 @@
+use crate::math::MintVec4;
 use crate::sys;
 use crate::Ui;
+use std::cmp::Reverse;
+use std::fmt;
+use std::ops::Range;
 use std::ptr;
 
 /// Helper to parse and apply text filters
@@ -104,3 +108,490 @@ impl Ui {
         TextFilter::new_with_filter(label, filter)
     }
 }
+
+/// A match of a [`SearchFilter`] against some text.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FilterMatch {
+    /// How well the text matched. Higher is better; only meaningful relative to other matches
+    /// of the same filter.
+    pub score: i32,
+    /// Byte ranges of the text which matched, sorted and non-overlapping.
+    pub ranges: Vec<Range<usize>>,
+}
+
+type CustomMatcher = dyn Fn(&str, &str) -> Option<FilterMatch>;
+
+/// How a [`SearchFilter`] matches plain (non-glob) terms.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MatchMode {
+    /// The term must appear in the text as is.
+    Substring,
+    /// The characters of the term must appear in the text in order, but not necessarily next
+    /// to each other.
+    Fuzzy,
+}
+
+#[derive(Clone, Debug)]
+struct Term {
+    pattern: Vec<char>,
+    glob: bool,
+}
+
+/// A filter implemented in Rust, with the same syntax as [`TextFilter`]: a comma-separated list
+/// of terms, where terms prefixed with `-` exclude matching text.
+///
+/// On top of that, terms containing `*` (any sequence of characters) or `?` (any single
+/// character) are glob patterns, plain terms can be matched fuzzily and case sensitively, and
+/// matches report a score and the matched byte ranges for highlighting.
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// # let lines = ["[info] started", "[error] timed out"];
+/// let mut filter = SearchFilter::new("Filter").mode(MatchMode::Fuzzy);
+/// filter.draw(ui);
+/// for line in lines {
+///     if let Some(found) = filter.find(line) {
+///         ui.text_highlighted(line, &found.ranges, [1.0, 0.8, 0.0, 1.0]);
+///     }
+/// }
+/// ```
+pub struct SearchFilter {
+    label: String,
+    input: String,
+    mode: MatchMode,
+    case_sensitive: bool,
+    matcher: Option<Box<CustomMatcher>>,
+    include: Vec<Term>,
+    exclude: Vec<Term>,
+}
+
+impl SearchFilter {
+    /// Creates an empty filter, drawn with the given label.
+    pub fn new(label: impl Into<String>) -> Self {
+        SearchFilter {
+            label: label.into(),
+            input: String::new(),
+            mode: MatchMode::Substring,
+            case_sensitive: false,
+            matcher: None,
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+
+    /// Sets the initial filter text.
+    #[inline]
+    pub fn filter(mut self, filter: impl Into<String>) -> Self {
+        self.set_filter(filter);
+        self
+    }
+
+    /// Sets how plain terms are matched.
+    ///
+    /// Defaults to [`MatchMode::Substring`].
+    #[inline]
+    pub fn mode(mut self, mode: MatchMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Enables/disables case sensitive matching.
+    ///
+    /// Disabled by default.
+    #[inline]
+    pub fn case_sensitive(mut self, value: bool) -> Self {
+        self.case_sensitive = value;
+        self
+    }
+
+    /// Replaces the matching of plain terms with a custom function, which receives a term and
+    /// the text and returns the match, if any. Glob terms are still matched by the filter.
+    #[inline]
+    pub fn matcher(
+        mut self,
+        matcher: impl Fn(&str, &str) -> Option<FilterMatch> + 'static,
+    ) -> Self {
+        self.matcher = Some(Box::new(matcher));
+        self
+    }
+
+    /// Returns the current filter text.
+    pub fn text(&self) -> &str {
+        &self.input
+    }
+
+    /// Replaces the filter text.
+    pub fn set_filter(&mut self, filter: impl Into<String>) {
+        self.input = filter.into();
+        self.parse();
+    }
+
+    /// Enables/disables case sensitive matching, for example from a checkbox.
+    pub fn set_case_sensitive(&mut self, value: bool) {
+        self.case_sensitive = value;
+    }
+
+    /// Returns true if case sensitive matching is enabled.
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// Clears the filter text.
+    pub fn clear(&mut self) {
+        self.set_filter(String::new());
+    }
+
+    /// Returns true if the filter has any terms.
+    pub fn is_active(&self) -> bool {
+        !self.include.is_empty() || !self.exclude.is_empty()
+    }
+
+    /// Draws an input text to edit the filter, returning true if it changed.
+    pub fn draw(&mut self, ui: &Ui) -> bool {
+        self.draw_with_size(ui, 0.0)
+    }
+
+    /// Draws an input text of the given width to edit the filter, returning true if it changed.
+    pub fn draw_with_size(&mut self, ui: &Ui, width: f32) -> bool {
+        if width != 0.0 {
+            ui.set_next_item_width(width);
+        }
+        let changed = ui.input_text(&self.label, &mut self.input).build();
+        if changed {
+            self.parse();
+        }
+        changed
+    }
+
+    /// Returns true if the text passes the filter.
+    pub fn pass_filter(&self, text: &str) -> bool {
+        self.find(text).is_some()
+    }
+
+    /// Matches the text against the filter.
+    ///
+    /// Text passes if it matches any include term (or there are none) and no exclude term. The
+    /// returned match combines all matching include terms, with the best score among them.
+    pub fn find(&self, text: &str) -> Option<FilterMatch> {
+        if self
+            .exclude
+            .iter()
+            .any(|term| self.find_term(term, text).is_some())
+        {
+            return None;
+        }
+        if self.include.is_empty() {
+            return Some(FilterMatch::default());
+        }
+        let mut result: Option<FilterMatch> = None;
+        for found in self
+            .include
+            .iter()
+            .filter_map(|term| self.find_term(term, text))
+        {
+            let result = result.get_or_insert(FilterMatch {
+                score: i32::MIN,
+                ranges: Vec::new(),
+            });
+            result.score = result.score.max(found.score);
+            result.ranges.extend(found.ranges);
+        }
+        result.map(|mut result| {
+            result.ranges = merge_ranges(result.ranges);
+            result
+        })
+    }
+
+    fn parse(&mut self) {
+        self.include.clear();
+        self.exclude.clear();
+        for term in self.input.split(',').map(str::trim) {
+            let (list, term) = match term.strip_prefix('-') {
+                Some(term) => (&mut self.exclude, term.trim_start()),
+                None => (&mut self.include, term),
+            };
+            if !term.is_empty() {
+                list.push(Term {
+                    pattern: term.chars().collect(),
+                    glob: term.contains(['*', '?']),
+                });
+            }
+        }
+    }
+
+    fn find_term(&self, term: &Term, text: &str) -> Option<FilterMatch> {
+        let eq = |a: char, b: char| {
+            a == b || !self.case_sensitive && a.to_lowercase().eq(b.to_lowercase())
+        };
+        let starts = text.char_indices().map(|(start, _)| start);
+        let positions = if term.glob {
+            starts
+                .chain(std::iter::once(text.len()))
+                .find_map(|start| glob_match(&term.pattern, text, start, &eq))?
+        } else if let Some(matcher) = &self.matcher {
+            let term: String = term.pattern.iter().collect();
+            return matcher(&term, text);
+        } else {
+            let candidates = starts.filter_map(|start| match self.mode {
+                MatchMode::Substring => substring_match(&term.pattern, text, start, &eq),
+                MatchMode::Fuzzy => fuzzy_match(&term.pattern, text, start, &eq),
+            });
+            candidates.max_by_key(|positions| (score(text, positions), Reverse(positions[0])))?
+        };
+        Some(FilterMatch {
+            score: score(text, &positions),
+            ranges: to_ranges(text, &positions),
+        })
+    }
+}
+
+impl fmt::Debug for SearchFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SearchFilter")
+            .field("label", &self.label)
+            .field("input", &self.input)
+            .field("mode", &self.mode)
+            .field("case_sensitive", &self.case_sensitive)
+            .field("matcher", &self.matcher.as_ref().map(|_| "<fn>"))
+            .finish()
+    }
+}
+
+// Matches return the byte offsets of the matched characters of the text.
+
+/// Returns the character of `text` starting at byte `pos`.
+fn char_at(text: &str, pos: usize) -> Option<char> {
+    text[pos..].chars().next()
+}
+
+/// Matches the pattern as a substring starting at `start`.
+fn substring_match(
+    pattern: &[char],
+    text: &str,
+    start: usize,
+    eq: &impl Fn(char, char) -> bool,
+) -> Option<Vec<usize>> {
+    let mut chars = text[start..].char_indices();
+    let mut positions = Vec::with_capacity(pattern.len());
+    for &p in pattern {
+        let (pos, c) = chars.next()?;
+        if !eq(c, p) {
+            return None;
+        }
+        positions.push(start + pos);
+    }
+    Some(positions)
+}
+
+/// Matches the pattern starting exactly at `start`, recording the positions of the characters
+/// matched by literals and `?`. `*` matches as few characters as possible.
+///
+/// This is the iterative wildcard match, which only backtracks to the last `*`.
+fn glob_match(
+    pattern: &[char],
+    text: &str,
+    start: usize,
+    eq: &impl Fn(char, char) -> bool,
+) -> Option<Vec<usize>> {
+    let mut positions = Vec::new();
+    let (mut p, mut pos) = (0, start);
+    // Pattern index after the last `*`, text position it is retried from, and matched positions.
+    let mut star: Option<(usize, usize, usize)> = None;
+    while p < pattern.len() {
+        if pattern[p] == '*' {
+            p += 1;
+            star = Some((p, pos, positions.len()));
+            continue;
+        }
+        match char_at(text, pos) {
+            Some(c) if pattern[p] == '?' || eq(c, pattern[p]) => {
+                positions.push(pos);
+                pos += c.len_utf8();
+                p += 1;
+            }
+            _ => {
+                // Let the last `*` match one more character, and retry the rest of the pattern.
+                let (star_p, star_pos, len) = star?;
+                let skipped = char_at(text, star_pos)?;
+                star = Some((star_p, star_pos + skipped.len_utf8(), len));
+                positions.truncate(len);
+                p = star_p;
+                pos = star_pos + skipped.len_utf8();
+            }
+        }
+    }
+    Some(positions)
+}
+
+/// Matches the pattern as a subsequence, with its first character at `start`.
+fn fuzzy_match(
+    pattern: &[char],
+    text: &str,
+    start: usize,
+    eq: &impl Fn(char, char) -> bool,
+) -> Option<Vec<usize>> {
+    let mut chars = text[start..].char_indices();
+    let mut positions = Vec::with_capacity(pattern.len());
+    for &p in pattern {
+        loop {
+            let (pos, c) = chars.next()?;
+            if eq(c, p) {
+                positions.push(start + pos);
+                break;
+            }
+            if positions.is_empty() {
+                return None;
+            }
+        }
+    }
+    Some(positions)
+}
+
+/// Scores matched character positions, favoring consecutive characters and word starts.
+fn score(text: &str, positions: &[usize]) -> i32 {
+    let mut score = 0;
+    for (i, &pos) in positions.iter().enumerate() {
+        let c = char_at(text, pos).unwrap_or_default();
+        score += 1;
+        if i > 0 {
+            let prev = positions[i - 1];
+            let prev_end = prev + char_at(text, prev).map_or(0, char::len_utf8);
+            let gap = text[prev_end..pos].chars().take(10).count();
+            score += if gap == 0 { 5 } else { -(gap as i32) };
+        }
+        let word_start = match text[..pos].chars().next_back() {
+            None => true,
+            Some(prev) => !prev.is_alphanumeric() || prev.is_lowercase() && c.is_uppercase(),
+        };
+        if word_start {
+            score += 10;
+        }
+    }
+    score
+}
+
+/// Converts sorted character positions to byte ranges, joining adjacent characters.
+fn to_ranges(text: &str, positions: &[usize]) -> Vec<Range<usize>> {
+    let ranges = positions
+        .iter()
+        .map(|&pos| pos..pos + char_at(text, pos).map_or(0, char::len_utf8));
+    merge_ranges(ranges.collect())
+}
+
+fn merge_ranges(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+impl Ui {
+    /// Renders text with the given byte ranges (such as [`FilterMatch::ranges`]) in another
+    /// color.
+    ///
+    /// Ranges past the end of the text are clipped, and ranges which don't start and end on
+    /// character boundaries are ignored.
+    pub fn text_highlighted(
+        &self,
+        text: &str,
+        ranges: &[Range<usize>],
+        color: impl Into<MintVec4>,
+    ) {
+        let color = color.into();
+        let mut pos = 0;
+        let mut first = true;
+        let mut segment = |ui: &Ui, range: Range<usize>, highlight: bool| {
+            if range.is_empty() {
+                return;
+            }
+            if !first {
+                ui.same_line_with_spacing(0.0, 0.0);
+            }
+            first = false;
+            if highlight {
+                ui.text_colored(color, &text[range]);
+            } else {
+                ui.text(&text[range]);
+            }
+        };
+        for range in ranges {
+            let range = range.start.min(text.len())..range.end.min(text.len());
+            if !text.is_char_boundary(range.start) || !text.is_char_boundary(range.end) {
+                continue;
+            }
+            segment(self, pos..range.start.max(pos), false);
+            segment(self, range.start.max(pos)..range.end.max(pos), true);
+            pos = pos.max(range.end);
+        }
+        segment(self, pos..text.len(), false);
+        if first {
+            self.text("");
+        }
+    }
+}
+
+#[test]
+fn test_search_filter_terms() {
+    let filter = SearchFilter::new("filter").filter("error, warn, -timeout");
+    assert!(filter.is_active());
+    assert!(filter.pass_filter("[ERROR] disk full"));
+    assert!(filter.pass_filter("warning: low memory"));
+    assert!(!filter.pass_filter("error: timeout"));
+    assert!(!filter.pass_filter("info: started"));
+    let found = filter.find("an error").unwrap();
+    assert_eq!((found.ranges.len(), &found.ranges[0]), (1, &(3..8)));
+
+    let filter = filter.case_sensitive(true);
+    assert!(!filter.pass_filter("[ERROR] disk full"));
+
+    let filter = SearchFilter::new("filter");
+    assert!(!filter.is_active());
+    assert_eq!(filter.find("anything"), Some(FilterMatch::default()));
+}
+
+#[test]
+fn test_search_filter_fuzzy_and_glob() {
+    let filter = SearchFilter::new("filter")
+        .filter("drwl")
+        .mode(MatchMode::Fuzzy);
+    let found = filter.find("DrawList").unwrap();
+    assert_eq!(found.ranges, [0..2, 3..5]);
+    assert!(found.score > filter.find("handrawl").unwrap().score);
+    assert!(!filter.pass_filter("lwrd"));
+
+    let filter = SearchFilter::new("filter").filter("fo?*.rs");
+    assert_eq!(filter.find("src/font.rs").unwrap().ranges, [4..7, 8..11]);
+    assert!(!filter.pass_filter("fo.rs"));
+    let filter = SearchFilter::new("filter").filter("a*b?d");
+    assert_eq!(filter.find("xxaxbcdx").unwrap().ranges, [2..3, 4..7]);
+    // Several `*` don't backtrack more than once per character.
+    let filter = SearchFilter::new("filter").filter("*a*a*a*a*a*a*a*b");
+    assert!(!filter.pass_filter(&"a".repeat(500)));
+
+    let filter = SearchFilter::new("filter")
+        .filter("xyz")
+        .matcher(|term, text| {
+            (term.len() == text.len()).then(|| FilterMatch {
+                score: 1,
+                ranges: std::iter::once(0..text.len()).collect(),
+            })
+        });
+    assert!(filter.pass_filter("abc"));
+    assert!(!filter.pass_filter("abcd"));
+}
+
+#[test]
+fn test_text_highlighted_ranges() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.new_frame();
+    // The first range ends inside `é` and is ignored, the second one is clipped.
+    ui.text_highlighted("héllo", &[1..2, 3..99], [1.0, 0.8, 0.0, 1.0]);
+    ctx.render();
+}
@@ -6,6 +6,7 @@ use crate::input::mouse::MouseButton;
 use crate::math::MintVec2;
 use crate::style::StyleColor;
 use crate::sys;
+use crate::ImColor32;
 use crate::Style;
 use crate::Ui;
 bitflags! {
@@ -236,6 +237,16 @@ impl Ui {
         unsafe { self.style() }.colors[style_color as usize]
     }
 
+    /// Returns a style color as Dear ImGui draws it, with the current style alpha applied and
+    /// multiplied by `alpha_mul`.
+    ///
+    /// Unlike [`Ui::style_color`], the color is faded inside [`Ui::begin_disabled`], so custom
+    /// widgets drawn with it look disabled like the built-in ones.
+    #[doc(alias = "GetColorU32")]
+    pub fn style_color_u32(&self, style_color: StyleColor, alpha_mul: f32) -> ImColor32 {
+        unsafe { sys::igGetColorU32_Col(style_color as i32, alpha_mul) }.into()
+    }
+
     /// Gets the name of some style color.
     ///
     /// This is just a wrapper around calling [`name`] on [StyleColor].
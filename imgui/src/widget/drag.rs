@@ -1,8 +1,13 @@
+use std::cmp::Ordering;
 use std::os::raw::c_void;
 use std::ptr;
 
-use crate::internal::DataTypeKind;
+use crate::internal::{DataType, DataTypeKind};
 use crate::sys;
+use crate::widget::scalar::{
+    edit_scalar, scalar_add, scalar_clamp, scalar_cmp, scalar_format, scalar_highest,
+    scalar_lowest, scalar_max, scalar_min, scalar_sub, scalar_to_f64, Primitive, Scalar,
+};
 use crate::widget::slider::SliderFlags;
 use crate::Ui;
 
@@ -18,7 +23,7 @@ pub struct Drag<T, L, F = &'static str> {
     flags: SliderFlags,
 }
 
-impl<L: AsRef<str>, T: DataTypeKind> Drag<T, L> {
+impl<L: AsRef<str>, T: Scalar> Drag<T, L> {
     /// Constructs a new drag slider builder.
     #[doc(alias = "DragScalar", alias = "DragScalarN")]
     pub fn new(label: L) -> Self {
@@ -33,8 +38,10 @@ impl<L: AsRef<str>, T: DataTypeKind> Drag<T, L> {
     }
 }
 
-impl<L: AsRef<str>, T: DataTypeKind, F: AsRef<str>> Drag<T, L, F> {
+impl<L: AsRef<str>, T: Scalar, F: AsRef<str>> Drag<T, L, F> {
     /// Sets the range (inclusive)
+    ///
+    /// An empty range, where `min` is not less than `max`, leaves the value unclamped.
     pub fn range(mut self, min: T, max: T) -> Self {
         self.min = Some(min);
         self.max = Some(max);
@@ -67,27 +74,30 @@ impl<L: AsRef<str>, T: DataTypeKind, F: AsRef<str>> Drag<T, L, F> {
     ///
     /// Returns true if the slider value was changed.
     pub fn build(self, ui: &Ui, value: &mut T) -> bool {
-        unsafe {
-            let (one, two) = ui.scratch_txt_with_opt(self.label, self.display_format);
-
+        let min = self.min.map(T::to_repr);
+        let max = self.max.map(T::to_repr);
+        let (one, two) =
+            ui.scratch_txt_with_opt(&self.label, scalar_format::<T>(&self.display_format));
+        edit_scalar(value, |value| unsafe {
             sys::igDragScalar(
                 one,
-                T::KIND as i32,
-                value as *mut T as *mut c_void,
+                T::Repr::KIND as i32,
+                value,
                 self.speed,
-                self.min
-                    .as_ref()
-                    .map(|min| min as *const T)
+                min.as_ref()
+                    .map(|min| min as *const T::Repr)
                     .unwrap_or(ptr::null()) as *const c_void,
-                self.max
-                    .as_ref()
-                    .map(|max| max as *const T)
+                max.as_ref()
+                    .map(|max| max as *const T::Repr)
                     .unwrap_or(ptr::null()) as *const c_void,
                 two,
                 self.flags.bits() as i32,
             )
-        }
+        })
     }
+}
+
+impl<L: AsRef<str>, T: DataTypeKind, F: AsRef<str>> Drag<T, L, F> {
     /// Builds a horizontal array of multiple drag sliders attached to the given slice.
     ///
     /// Returns true if any slider value was changed.
@@ -124,6 +134,7 @@ pub struct DragRange<T, L, F = &'static str, M = &'static str> {
     speed: f32,
     min: Option<T>,
     max: Option<T>,
+    min_gap: Option<T>,
     display_format: Option<F>,
     max_display_format: Option<M>,
     flags: SliderFlags,
@@ -138,6 +149,7 @@ impl<T: DataTypeKind, L: AsRef<str>> DragRange<T, L> {
             speed: 1.0,
             min: None,
             max: None,
+            min_gap: None,
             display_format: None,
             max_display_format: None,
             flags: SliderFlags::empty(),
@@ -147,11 +159,14 @@ impl<T: DataTypeKind, L: AsRef<str>> DragRange<T, L> {
 
 impl<T, L, F, M> DragRange<T, L, F, M>
 where
-    T: DataTypeKind,
+    T: Primitive,
     L: AsRef<str>,
     F: AsRef<str>,
     M: AsRef<str>,
 {
+    /// Sets the range (inclusive) both values are clamped to.
+    ///
+    /// An empty range, where `min` is not less than `max`, leaves the values unclamped.
     pub fn range(mut self, min: T, max: T) -> Self {
         self.min = Some(min);
         self.max = Some(max);
@@ -164,6 +179,13 @@ where
         self.speed = speed;
         self
     }
+    /// Sets the smallest allowed difference between the max and min values.
+    ///
+    /// Defaults to no gap, allowing both values to be equal.
+    pub fn min_gap(mut self, min_gap: T) -> Self {
+        self.min_gap = Some(min_gap);
+        self
+    }
     /// Sets the display format using *a C-style printf string*
     pub fn display_format<F2: AsRef<str>>(self, display_format: F2) -> DragRange<T, L, F2, M> {
         DragRange {
@@ -171,6 +193,7 @@ where
             speed: self.speed,
             min: self.min,
             max: self.max,
+            min_gap: self.min_gap,
             display_format: Some(display_format),
             max_display_format: self.max_display_format,
             flags: self.flags,
@@ -186,6 +209,7 @@ where
             speed: self.speed,
             min: self.min,
             max: self.max,
+            min_gap: self.min_gap,
             display_format: self.display_format,
             max_display_format: Some(max_display_format),
             flags: self.flags,
@@ -196,90 +220,136 @@ where
         self.flags = flags;
         self
     }
-}
-
-impl<L, F, M> DragRange<f32, L, F, M>
-where
-    L: AsRef<str>,
-    F: AsRef<str>,
-    M: AsRef<str>,
-{
     /// Builds a drag range slider that is bound to the given min/max values.
     ///
+    /// Each value is clamped to the range, if any, and kept on its side of the other value.
+    ///
+    /// `f32` and `i32` ranges without a [`min_gap`](Self::min_gap) use Dear ImGui's
+    /// `DragFloatRange2` and `DragIntRange2`. Other ranges are built from two [`Drag`]s followed
+    /// by the label, laid out the same way.
+    ///
     /// Returns true if the slider value was changed.
-    #[doc(alias = "DragFloatRange2")]
-    pub fn build(self, ui: &Ui, min: &mut f32, max: &mut f32) -> bool {
-        // we do this ourselves the long way...
-        unsafe {
-            let buffer = &mut *ui.scratch_buffer().get();
-            buffer.refresh_buffer();
+    #[doc(alias = "DragIntRange2", alias = "DragFloatRange2")]
+    pub fn build(self, ui: &Ui, min: &mut T, max: &mut T) -> bool {
+        if self.min_gap.is_none() {
+            if let Some(changed) = self.build_native(ui, min, max) {
+                return changed;
+            }
+        }
+        let (lower, upper) = match (self.min, self.max) {
+            (Some(lower), Some(upper)) if scalar_cmp(lower, upper) == Some(Ordering::Less) => {
+                (lower, upper)
+            }
+            _ => (scalar_lowest(), scalar_highest()),
+        };
+        let gap = self.min_gap;
+        let with_gap = |value: T, apply: fn(T, T) -> T| gap.map_or(value, |gap| apply(value, gap));
+        let label = self.label.as_ref();
 
-            let label_start = buffer.push(self.label);
-            let display_format = self.display_format.as_ref().map(|v| buffer.push(v));
-            let max_display_format = self.max_display_format.as_ref().map(|v| buffer.push(v));
+        let _id = ui.push_id(label);
+        let _group = ui.begin_group();
+        let spacing = ui.clone_style().item_inner_spacing[0];
+        let width = ((ui.calc_item_width() - spacing) / 2.0).max(1.0);
 
-            let label = buffer.offset(label_start);
-            let display_format = display_format
-                .map(|v| buffer.offset(v))
-                .unwrap_or_else(std::ptr::null);
-            let max_display_format = max_display_format
-                .map(|v| buffer.offset(v))
-                .unwrap_or_else(std::ptr::null);
+        let min_upper = scalar_min(upper, with_gap(*max, scalar_sub));
+        let drag = Drag::new("##min")
+            .speed(self.speed)
+            .range(lower, min_upper)
+            .flags(self.flags);
+        ui.set_next_item_width(width);
+        let mut changed = match &self.display_format {
+            Some(format) => drag.display_format(format).build(ui, min),
+            None => drag.build(ui, min),
+        };
+        // Typed values and empty ranges aren't clamped by Dear ImGui.
+        changed |= scalar_clamp(min, lower, min_upper);
+        ui.same_line_with_spacing(0.0, spacing);
 
-            sys::igDragFloatRange2(
-                label,
-                min as *mut f32,
-                max as *mut f32,
-                self.speed,
-                self.min.unwrap_or(0.0),
-                self.max.unwrap_or(0.0),
-                display_format,
-                max_display_format,
-                self.flags.bits() as i32,
-            )
+        let max_lower = scalar_max(lower, with_gap(*min, scalar_add));
+        let drag = Drag::new("##max")
+            .speed(self.speed)
+            .range(max_lower, upper)
+            .flags(self.flags);
+        ui.set_next_item_width(width);
+        let format = self.max_display_format.as_ref().map(AsRef::as_ref);
+        changed |= match format.or(self.display_format.as_ref().map(AsRef::as_ref)) {
+            Some(format) => drag.display_format(format).build(ui, max),
+            None => drag.build(ui, max),
+        };
+        changed |= scalar_clamp(max, max_lower, upper);
+
+        let visible = label.split("##").next().unwrap_or_default();
+        if !visible.is_empty() {
+            ui.same_line_with_spacing(0.0, spacing);
+            ui.text(visible);
         }
+        changed
     }
-}
 
-impl<L, F, M> DragRange<i32, L, F, M>
-where
-    L: AsRef<str>,
-    F: AsRef<str>,
-    M: AsRef<str>,
-{
-    /// Builds a drag range slider that is bound to the given min/max values.
-    ///
-    /// Returns true if the slider value was changed.
-    #[doc(alias = "DragIntRange2")]
-    pub fn build(self, ui: &Ui, min: &mut i32, max: &mut i32) -> bool {
-        unsafe {
-            // we do this ourselves the long way...
+    /// Builds `f32` and `i32` ranges with Dear ImGui's own range widgets, returning `None` for
+    /// other types.
+    fn build_native(&self, ui: &Ui, min: &mut T, max: &mut T) -> Option<bool> {
+        // Zero for both bounds leaves the values unclamped, as with the other drag widgets. The
+        // bounds are only converted for `f32` and `i32`, which `f64` holds exactly.
+        let bounds = (
+            self.min.map_or(0.0, scalar_to_f64),
+            self.max.map_or(0.0, scalar_to_f64),
+        );
+        // we do this ourselves the long way...
+        let (label, display_format, max_display_format) = unsafe {
             let buffer = &mut *ui.scratch_buffer().get();
             buffer.refresh_buffer();
 
-            let label_start = buffer.push(self.label);
+            let label_start = buffer.push(&self.label);
             let display_format = self.display_format.as_ref().map(|v| buffer.push(v));
             let max_display_format = self.max_display_format.as_ref().map(|v| buffer.push(v));
 
-            let label = buffer.offset(label_start);
-            let display_format = display_format
-                .map(|v| buffer.offset(v))
-                .unwrap_or_else(std::ptr::null);
-            let max_display_format = max_display_format
-                .map(|v| buffer.offset(v))
-                .unwrap_or_else(std::ptr::null);
-
-            sys::igDragIntRange2(
-                label,
-                min as *mut i32,
-                max as *mut i32,
-                self.speed,
-                self.min.unwrap_or(0),
-                self.max.unwrap_or(0),
-                display_format,
-                max_display_format,
-                self.flags.bits() as i32,
+            (
+                buffer.offset(label_start),
+                display_format
+                    .map(|v| buffer.offset(v))
+                    .unwrap_or_else(std::ptr::null),
+                max_display_format
+                    .map(|v| buffer.offset(v))
+                    .unwrap_or_else(std::ptr::null),
             )
+        };
+        // `Primitive` is only implemented for the primitive types, so `T` is the type of `KIND`.
+        let (min, max) = (min as *mut T, max as *mut T);
+        match T::KIND {
+            DataType::F32 => {
+                let (min, max) = (min as *mut f32, max as *mut f32);
+                Some(unsafe {
+                    sys::igDragFloatRange2(
+                        label,
+                        min,
+                        max,
+                        self.speed,
+                        bounds.0 as f32,
+                        bounds.1 as f32,
+                        display_format,
+                        max_display_format,
+                        self.flags.bits() as i32,
+                    )
+                })
+            }
+            DataType::I32 => {
+                let (min, max) = (min as *mut i32, max as *mut i32);
+                Some(unsafe {
+                    sys::igDragIntRange2(
+                        label,
+                        min,
+                        max,
+                        self.speed,
+                        bounds.0 as i32,
+                        bounds.1 as i32,
+                        display_format,
+                        max_display_format,
+                        self.flags.bits() as i32,
+                    )
+                })
+            }
+            _ => None,
         }
     }
 }
@@ -0,0 +1,614 @@
+use std::cmp::Ordering;
+use std::f32::consts::{PI, TAU};
+
+use crate::style::StyleColor;
+use crate::widget::scalar::Primitive;
+use crate::widget::scalar::{scalar_cmp, scalar_display, scalar_from_f64, scalar_to_f64};
+use crate::widget::slider::SliderFlags;
+use crate::{DrawListMut, ImColor32, Ui};
+
+/// Angle of the lowest value of knobs and gauges, at the bottom left.
+const DEFAULT_START_ANGLE: f32 = 0.75 * PI;
+/// Angle of the highest value of knobs and gauges, at the bottom right.
+const DEFAULT_END_ANGLE: f32 = 2.25 * PI;
+
+impl Ui {
+    /// Creates a new knob widget. Returns true if the value has been edited.
+    pub fn knob<T: AsRef<str>, K: Primitive>(
+        &self,
+        label: T,
+        min: K,
+        max: K,
+        value: &mut K,
+    ) -> bool {
+        self.knob_config(label, min, max).build(value)
+    }
+
+    /// Creates an unbuilt knob, see [`Knob`].
+    pub fn knob_config<T: AsRef<str>, K: Primitive>(
+        &self,
+        label: T,
+        min: K,
+        max: K,
+    ) -> Knob<'_, T, K> {
+        Knob {
+            label,
+            min,
+            max,
+            angles: [DEFAULT_START_ANGLE, DEFAULT_END_ANGLE],
+            detents: 0,
+            radius: None,
+            display_format: None,
+            flags: SliderFlags::empty(),
+            ui: self,
+        }
+    }
+
+    /// Creates a new gauge showing the value.
+    pub fn gauge<T: AsRef<str>, K: Primitive>(&self, label: T, min: K, max: K, value: K) {
+        self.gauge_config(label, min, max).build(value)
+    }
+
+    /// Creates an unbuilt gauge, see [`Gauge`].
+    pub fn gauge_config<T: AsRef<str>, K: Primitive>(
+        &self,
+        label: T,
+        min: K,
+        max: K,
+    ) -> Gauge<'_, T, K> {
+        Gauge {
+            label,
+            min,
+            max,
+            angles: [DEFAULT_START_ANGLE, DEFAULT_END_ANGLE],
+            radius: None,
+            bands: Vec::new(),
+            display_format: None,
+            flags: SliderFlags::empty(),
+            ui: self,
+        }
+    }
+}
+
+/// Builder for a rotary knob widget, edited by dragging the indicator around the knob.
+///
+/// The label is shown under the knob, and the value in a tooltip.
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let mut cutoff = 1000.0f32;
+/// let mut octave = 0i32;
+/// ui.knob_config("Cutoff", 20.0, 20000.0)
+///     .flags(SliderFlags::LOGARITHMIC)
+///     .display_format("%.0f Hz")
+///     .build(&mut cutoff);
+/// ui.same_line();
+/// ui.knob_config("Octave", -2, 2).detents(5).build(&mut octave);
+/// ```
+#[derive(Copy, Clone, Debug)]
+#[must_use]
+pub struct Knob<'ui, Label, Data, Format = &'static str> {
+    label: Label,
+    min: Data,
+    max: Data,
+    angles: [f32; 2],
+    detents: u32,
+    radius: Option<f32>,
+    display_format: Option<Format>,
+    flags: SliderFlags,
+    ui: &'ui Ui,
+}
+
+impl<'ui, Label, Data, Format> Knob<'ui, Label, Data, Format>
+where
+    Label: AsRef<str>,
+    Data: Primitive,
+    Format: AsRef<str>,
+{
+    /// Sets the range (inclusive) of the knob.
+    #[inline]
+    pub fn range(mut self, min: Data, max: Data) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+    /// Sets the angles (in radians) of the minimum and maximum values, measured clockwise from
+    /// the right of the knob. `max_angle` must be greater than `min_angle`, by at most a turn.
+    ///
+    /// Defaults to a three-quarter turn from the bottom left to the bottom right.
+    #[inline]
+    pub fn angle_range(mut self, min_angle: f32, max_angle: f32) -> Self {
+        self.angles = [min_angle, max_angle];
+        self
+    }
+    /// Snaps the knob to the given number of evenly spaced positions, including both ends of
+    /// the range, which are marked around the knob.
+    ///
+    /// Defaults to 0, turning the knob continuously.
+    #[inline]
+    pub fn detents(mut self, detents: u32) -> Self {
+        self.detents = detents;
+        self
+    }
+    /// Sets the radius of the knob.
+    ///
+    /// Defaults to the frame height, which is the font size plus the vertical frame padding.
+    #[inline]
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+    /// Sets the display format of the value using *a C-style printf string*.
+    ///
+    /// Only the precision of the conversion is used, as in `"%.1f dB"`.
+    #[inline]
+    pub fn display_format<Format2: AsRef<str>>(
+        self,
+        display_format: Format2,
+    ) -> Knob<'ui, Label, Data, Format2> {
+        Knob {
+            label: self.label,
+            min: self.min,
+            max: self.max,
+            angles: self.angles,
+            detents: self.detents,
+            radius: self.radius,
+            display_format: Some(display_format),
+            flags: self.flags,
+            ui: self.ui,
+        }
+    }
+    /// Replaces all current settings with the given flags.
+    ///
+    /// Only [`SliderFlags::LOGARITHMIC`] applies to knobs; it is ignored unless both ends of the
+    /// range are non-zero and have the same sign.
+    #[inline]
+    pub fn flags(mut self, flags: SliderFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+    /// Builds a knob that is bound to the given value.
+    ///
+    /// Returns true if the knob value was changed.
+    pub fn build(self, value: &mut Data) -> bool {
+        let ui = self.ui;
+        let label = self.label.as_ref();
+        let radius = self.radius.unwrap_or_else(|| ui.frame_height());
+        let scale = Scale::new(self.min, self.max, self.flags);
+
+        let _group = ui.begin_group();
+        let dial = Dial::layout(ui, label, radius, self.angles);
+        ui.invisible_button(label, dial.size);
+        let (hovered, active) = (ui.is_item_hovered(), ui.is_item_active());
+
+        let mut changed = false;
+        let [x, y] = ui.io().mouse_pos;
+        let offset = [x - dial.center[0], y - dial.center[1]];
+        // The angle of the mouse is unstable close to the center.
+        if active && offset[0].hypot(offset[1]) > radius * 0.2 {
+            let mut t = dial.fraction_at(offset[1].atan2(offset[0]));
+            if self.detents > 1 {
+                let steps = (self.detents - 1) as f32;
+                t = (t * steps).round() / steps;
+            }
+            let new_value = scale.value(t);
+            if scalar_cmp(new_value, *value) != Some(Ordering::Equal) {
+                *value = new_value;
+                changed = true;
+            }
+        }
+
+        let draw_list = ui.get_window_draw_list();
+        let background = if active {
+            StyleColor::FrameBgActive
+        } else if hovered {
+            StyleColor::FrameBgHovered
+        } else {
+            StyleColor::FrameBg
+        };
+        draw_list
+            .add_circle(dial.center, radius, ui.style_color(background))
+            .filled(true)
+            .build();
+        let thickness = (radius * 0.12).max(2.0);
+        let track_radius = radius * 0.78;
+        let t = scale.fraction(*value);
+        dial.arc(
+            &draw_list,
+            0.0,
+            1.0,
+            track_radius,
+            thickness,
+            ui.style_color(StyleColor::TextDisabled),
+        );
+        let grab = if active {
+            StyleColor::SliderGrabActive
+        } else {
+            StyleColor::SliderGrab
+        };
+        dial.arc(
+            &draw_list,
+            0.0,
+            t,
+            track_radius,
+            thickness,
+            ui.style_color(grab),
+        );
+        if self.detents > 1 {
+            let steps = (self.detents - 1) as f32;
+            for step in 0..self.detents {
+                let t = step as f32 / steps;
+                draw_list
+                    .add_line(
+                        dial.point(t, radius * 0.92),
+                        dial.point(t, radius),
+                        ui.style_color(StyleColor::Text),
+                    )
+                    .build();
+            }
+        }
+        draw_list
+            .add_line(
+                dial.point(t, radius * 0.25),
+                dial.point(t, track_radius),
+                ui.style_color(StyleColor::Text),
+            )
+            .thickness(thickness)
+            .build();
+        drop(draw_list);
+
+        dial.label(ui, label);
+        if hovered || active {
+            let format = self.display_format.as_ref().map(AsRef::as_ref);
+            ui.tooltip_text(scalar_display(*value, format));
+        }
+        changed
+    }
+}
+
+/// Builder for a read-only circular gauge, showing a value with a needle over optional colored
+/// bands.
+///
+/// The label is shown under the gauge.
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let temperature = 72.5f32;
+/// ui.gauge_config("Motor", 0.0, 120.0)
+///     .band(80.0, 100.0, [1.0, 0.8, 0.0, 1.0])
+///     .band(100.0, 120.0, [1.0, 0.2, 0.2, 1.0])
+///     .display_format("%.1f C")
+///     .build(temperature);
+/// ```
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct Gauge<'ui, Label, Data, Format = &'static str> {
+    label: Label,
+    min: Data,
+    max: Data,
+    angles: [f32; 2],
+    radius: Option<f32>,
+    bands: Vec<(Data, Data, ImColor32)>,
+    display_format: Option<Format>,
+    flags: SliderFlags,
+    ui: &'ui Ui,
+}
+
+impl<'ui, Label, Data, Format> Gauge<'ui, Label, Data, Format>
+where
+    Label: AsRef<str>,
+    Data: Primitive,
+    Format: AsRef<str>,
+{
+    /// Sets the range (inclusive) of the gauge.
+    #[inline]
+    pub fn range(mut self, min: Data, max: Data) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+    /// Sets the angles (in radians) of the minimum and maximum values, measured clockwise from
+    /// the right of the gauge. `max_angle` must be greater than `min_angle`, by at most a turn.
+    ///
+    /// Defaults to a three-quarter turn from the bottom left to the bottom right.
+    #[inline]
+    pub fn angle_range(mut self, min_angle: f32, max_angle: f32) -> Self {
+        self.angles = [min_angle, max_angle];
+        self
+    }
+    /// Sets the radius of the gauge.
+    ///
+    /// Defaults to twice the frame height, which is the font size plus the vertical frame
+    /// padding.
+    #[inline]
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+    /// Adds a band of the given color along the rim of the gauge, between two values. Bands
+    /// are drawn in the order they are added.
+    #[inline]
+    pub fn band(mut self, from: Data, to: Data, color: impl Into<ImColor32>) -> Self {
+        self.bands.push((from, to, color.into()));
+        self
+    }
+    /// Sets the display format of the value using *a C-style printf string*.
+    ///
+    /// Only the precision of the conversion is used, as in `"%.1f bar"`.
+    #[inline]
+    pub fn display_format<Format2: AsRef<str>>(
+        self,
+        display_format: Format2,
+    ) -> Gauge<'ui, Label, Data, Format2> {
+        Gauge {
+            label: self.label,
+            min: self.min,
+            max: self.max,
+            angles: self.angles,
+            radius: self.radius,
+            bands: self.bands,
+            display_format: Some(display_format),
+            flags: self.flags,
+            ui: self.ui,
+        }
+    }
+    /// Replaces all current settings with the given flags.
+    ///
+    /// Only [`SliderFlags::LOGARITHMIC`] applies to gauges; it is ignored unless both ends of
+    /// the range are non-zero and have the same sign.
+    #[inline]
+    pub fn flags(mut self, flags: SliderFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+    /// Builds a gauge showing the given value.
+    pub fn build(self, value: Data) {
+        let ui = self.ui;
+        let label = self.label.as_ref();
+        let radius = self.radius.unwrap_or_else(|| ui.frame_height() * 2.0);
+        let scale = Scale::new(self.min, self.max, self.flags);
+
+        let _group = ui.begin_group();
+        let dial = Dial::layout(ui, label, radius, self.angles);
+        ui.dummy(dial.size);
+
+        let draw_list = ui.get_window_draw_list();
+        draw_list
+            .add_circle(dial.center, radius, ui.style_color(StyleColor::FrameBg))
+            .filled(true)
+            .build();
+        let thickness = (radius * 0.1).max(2.0);
+        let rim_radius = radius - thickness;
+        dial.arc(
+            &draw_list,
+            0.0,
+            1.0,
+            rim_radius,
+            thickness,
+            ui.style_color(StyleColor::TextDisabled),
+        );
+        for &(from, to, color) in &self.bands {
+            let (from, to) = (scale.fraction(from), scale.fraction(to));
+            dial.arc(&draw_list, from, to, rim_radius, thickness, color);
+        }
+        let t = scale.fraction(value);
+        draw_list
+            .add_line(
+                dial.center,
+                dial.point(t, rim_radius - thickness),
+                ui.style_color(StyleColor::SliderGrabActive),
+            )
+            .thickness((thickness * 0.5).max(1.0))
+            .build();
+        draw_list
+            .add_circle(dial.center, thickness, ui.style_color(StyleColor::Text))
+            .filled(true)
+            .build();
+        let format = self.display_format.as_ref().map(AsRef::as_ref);
+        let text = scalar_display(value, format);
+        let text_size = ui.calc_text_size(&text);
+        let text_pos = [
+            dial.center[0] - text_size[0] / 2.0,
+            dial.center[1] + radius * 0.4,
+        ];
+        draw_list.add_text(text_pos, ui.style_color(StyleColor::Text), text);
+        drop(draw_list);
+
+        dial.label(ui, label);
+    }
+}
+
+/// Maps values of a knob or gauge to fractions of its angle range.
+struct Scale {
+    min: f64,
+    max: f64,
+    logarithmic: bool,
+}
+
+impl Scale {
+    fn new<T: Primitive>(min: T, max: T, flags: SliderFlags) -> Self {
+        let (min, max) = (scalar_to_f64(min), scalar_to_f64(max));
+        Scale {
+            min,
+            max,
+            logarithmic: flags.contains(SliderFlags::LOGARITHMIC) && min * max > 0.0,
+        }
+    }
+
+    /// Returns the fraction of the range at which the value is, clamped to `0.0..=1.0`.
+    fn fraction<T: Primitive>(&self, value: T) -> f32 {
+        let value = scalar_to_f64(value);
+        let t = if self.logarithmic {
+            (value / self.min).ln() / (self.max / self.min).ln()
+        } else {
+            (value - self.min) / (self.max - self.min)
+        };
+        if t.is_nan() {
+            0.0
+        } else {
+            t.clamp(0.0, 1.0) as f32
+        }
+    }
+
+    /// Returns the value at the given fraction of the range.
+    fn value<T: Primitive>(&self, t: f32) -> T {
+        let t = f64::from(t);
+        let value = if self.logarithmic {
+            self.min * (self.max / self.min).powf(t)
+        } else {
+            self.min + (self.max - self.min) * t
+        };
+        scalar_from_f64(value)
+    }
+}
+
+/// Layout of a circular widget with its label underneath.
+struct Dial {
+    /// Top left corner of the widget, in screen coordinates.
+    pos: [f32; 2],
+    /// Size of the widget without its label.
+    size: [f32; 2],
+    center: [f32; 2],
+    start: f32,
+    sweep: f32,
+}
+
+impl Dial {
+    fn layout(ui: &Ui, label: &str, radius: f32, [start, end]: [f32; 2]) -> Self {
+        let padding = ui.clone_style().frame_padding;
+        let label_width = ui.calc_text_size(visible_label(label))[0];
+        let width = (radius * 2.0 + padding[0] * 2.0).max(label_width);
+        let size = [width, radius * 2.0 + padding[1] * 2.0];
+        let pos = ui.cursor_screen_pos();
+        Dial {
+            pos,
+            size,
+            center: [pos[0] + width / 2.0, pos[1] + padding[1] + radius],
+            start,
+            sweep: (end - start).clamp(f32::EPSILON, TAU),
+        }
+    }
+
+    /// Returns the point at the given fraction of the angle range and distance from the center.
+    fn point(&self, t: f32, radius: f32) -> [f32; 2] {
+        let angle = self.start + self.sweep * t;
+        [
+            self.center[0] + radius * angle.cos(),
+            self.center[1] + radius * angle.sin(),
+        ]
+    }
+
+    /// Returns the fraction of the angle range pointing towards `angle`. Angles outside of the
+    /// range give the nearest end.
+    fn fraction_at(&self, angle: f32) -> f32 {
+        let relative = (angle - self.start).rem_euclid(TAU);
+        if relative <= self.sweep {
+            relative / self.sweep
+        } else if relative - self.sweep < TAU - relative {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn arc(
+        &self,
+        draw_list: &DrawListMut<'_>,
+        from: f32,
+        to: f32,
+        radius: f32,
+        thickness: f32,
+        color: impl Into<ImColor32>,
+    ) {
+        let sweep = (to - from) * self.sweep;
+        if sweep <= 0.0 {
+            return;
+        }
+        let segments = ((sweep * radius / 4.0).ceil() as usize).clamp(2, 64);
+        let points = (0..=segments)
+            .map(|segment| {
+                self.point(
+                    from + (to - from) * segment as f32 / segments as f32,
+                    radius,
+                )
+            })
+            .collect();
+        draw_list
+            .add_polyline(points, color)
+            .thickness(thickness)
+            .build();
+    }
+
+    /// Shows the visible part of the label centered under the widget.
+    fn label(&self, ui: &Ui, label: &str) {
+        let label = visible_label(label);
+        if !label.is_empty() {
+            let width = ui.calc_text_size(label)[0];
+            let y = ui.cursor_screen_pos()[1];
+            ui.set_cursor_screen_pos([self.pos[0] + (self.size[0] - width) / 2.0, y]);
+            ui.text(label);
+        }
+    }
+}
+
+fn visible_label(label: &str) -> &str {
+    label.split("##").next().unwrap_or_default()
+}
+
+#[test]
+fn test_knob_scale() {
+    let linear = Scale::new(-2i32, 2, SliderFlags::LOGARITHMIC);
+    assert_eq!(linear.value::<i32>(0.75), 1);
+    assert_eq!(linear.fraction(5), 1.0);
+    let logarithmic = Scale::new(10.0f64, 1000.0, SliderFlags::LOGARITHMIC);
+    assert!((logarithmic.value::<f64>(0.5) - 100.0).abs() < 1e-9);
+    assert!((logarithmic.fraction(100.0f64) - 0.5).abs() < 1e-6);
+    let unsigned = Scale::new(0usize, 10, SliderFlags::empty());
+    assert_eq!(unsigned.value::<usize>(0.25), 3);
+    assert_eq!(unsigned.fraction(5usize), 0.5);
+    assert_eq!(unsigned.fraction(20usize), 1.0);
+}
+
+#[test]
+fn test_knob_drag() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.io_mut().config_input_trickle_event_queue = false;
+    let mut value = 0i32;
+    for frame in 0..4 {
+        // Hover right of the center of the knob, which is past the window padding and the
+        // frame padding.
+        if frame == 0 {
+            let padding = ctx.style().frame_padding;
+            let center = [8.0 + padding[0] + 20.0, 8.0 + padding[1] + 20.0];
+            ctx.io_mut()
+                .add_mouse_pos_event([center[0] + 15.0, center[1]]);
+        }
+        if frame == 2 {
+            ctx.io_mut()
+                .add_mouse_button_event(crate::MouseButton::Left, true);
+        }
+        let ui = ctx.new_frame();
+        ui.window("knob")
+            .position([0.0, 0.0], crate::Condition::Always)
+            .size([200.0, 200.0], crate::Condition::Always)
+            .no_decoration()
+            .build(|| {
+                let changed = ui
+                    .knob_config("##knob", 0, 12)
+                    .detents(13)
+                    .radius(20.0)
+                    .build(&mut value);
+                assert_eq!(changed, frame == 2);
+                ui.gauge_config("gauge", 0, 12)
+                    .band(9, 12, [1.0, 0.0, 0.0, 1.0])
+                    .build(value);
+            });
+        ctx.render();
+    }
+    // Right of the center is 5/6 of the default three-quarter turn.
+    assert_eq!(value, 10);
+}
@@ -2,9 +2,13 @@ use bitflags::bitflags;
 use std::ops::{BitAnd, BitAndAssign, BitOrAssign, Not};
 
 use crate::math::MintVec2;
+use crate::style::StyleColor;
 use crate::sys;
 use crate::{Direction, Ui};
 
+/// Time in seconds the thumb of a toggle switch takes to slide across.
+const TOGGLE_ANIMATION_TIME: f32 = 0.08;
+
 bitflags!(
     /// Flags for invisible buttons
     #[repr(transparent)]
@@ -106,6 +110,82 @@ impl Ui {
         }
         pressed
     }
+    /// Renders a toggle switch, with a thumb sliding right when it is on, followed by the
+    /// label.
+    ///
+    /// Returns true if this switch was clicked.
+    pub fn toggle(&self, label: impl AsRef<str>, value: &mut bool) -> bool {
+        let label = label.as_ref();
+        let visible = label.split("##").next().unwrap_or_default();
+        let style = self.clone_style();
+        let height = self.frame_height();
+        let width = height * 1.75;
+        let label_width = self.calc_text_size(visible)[0];
+        let size = if label_width > 0.0 {
+            [width + style.item_inner_spacing[0] + label_width, height]
+        } else {
+            [width, height]
+        };
+
+        let pos = self.cursor_screen_pos();
+        let clicked = self.invisible_button(label, size);
+        if clicked {
+            *value = !*value;
+        }
+        let hovered = self.is_item_hovered();
+        // Position of the thumb, from 0.0 when off to 1.0 when on.
+        let target = if *value { 1.0 } else { 0.0 };
+        let step = self.io().delta_time / TOGGLE_ANIMATION_TIME;
+        let t = self.with_item_state(self.new_id_str(label), |t: &mut Option<f32>| {
+            let t = t.get_or_insert(target);
+            *t = if *t < target {
+                (*t + step).min(target)
+            } else {
+                (*t - step).max(target)
+            };
+            *t
+        });
+
+        let draw_list = self.get_window_draw_list();
+        let radius = height / 2.0;
+        let end = [pos[0] + width, pos[1] + height];
+        let background = if hovered {
+            StyleColor::FrameBgHovered
+        } else {
+            StyleColor::FrameBg
+        };
+        draw_list
+            .add_rect(pos, end, self.style_color_u32(background, 1.0))
+            .filled(true)
+            .rounding(radius)
+            .build();
+        draw_list
+            .add_rect(pos, end, self.style_color_u32(StyleColor::CheckMark, t))
+            .filled(true)
+            .rounding(radius)
+            .build();
+        let thumb = [pos[0] + radius + (width - height) * t, pos[1] + radius];
+        draw_list
+            .add_circle(
+                thumb,
+                (radius - 2.0).max(1.0),
+                self.style_color_u32(StyleColor::Text, 1.0),
+            )
+            .filled(true)
+            .build();
+        if label_width > 0.0 {
+            let text_pos = [
+                end[0] + style.item_inner_spacing[0],
+                pos[1] + style.frame_padding[1],
+            ];
+            draw_list.add_text(
+                text_pos,
+                self.style_color_u32(StyleColor::Text, 1.0),
+                visible,
+            );
+        }
+        clicked
+    }
     /// Renders a simple radio button.
     ///
     /// Returns true if this radio button was clicked.
@@ -133,3 +213,34 @@ impl Ui {
         unsafe { sys::igBullet() };
     }
 }
+
+#[test]
+fn test_toggle() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.io_mut().config_input_trickle_event_queue = false;
+    let mut value = false;
+    for (frame, disabled) in (0..8).zip([false, false, false, false, true, true, true, true]) {
+        // Hover the switch, then click it on the third frame of each half.
+        match frame % 4 {
+            0 => ctx.io_mut().add_mouse_pos_event([12.0, 12.0]),
+            2 => ctx
+                .io_mut()
+                .add_mouse_button_event(crate::MouseButton::Left, true),
+            3 => ctx
+                .io_mut()
+                .add_mouse_button_event(crate::MouseButton::Left, false),
+            _ => {}
+        }
+        let ui = ctx.new_frame();
+        ui.window("toggle")
+            .position([0.0, 0.0], crate::Condition::Always)
+            .no_decoration()
+            .build(|| {
+                let _disabled = ui.begin_disabled(disabled);
+                let clicked = ui.toggle("switch", &mut value);
+                assert_eq!(clicked, frame == 3);
+            });
+        ctx.render();
+    }
+    assert!(value);
+}
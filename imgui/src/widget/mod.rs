@@ -2,12 +2,15 @@ pub mod color_editors;
 pub mod combo_box;
 pub mod drag;
 pub mod image;
+pub mod knob;
 pub mod list_box;
 pub mod menu;
 pub mod misc;
 pub mod progress_bar;
+pub mod scalar;
 pub mod selectable;
 pub mod slider;
+pub mod spinner;
 pub mod tab;
 pub mod text;
 pub mod tree;
@@ -16,10 +16,22 @@ use crate::Ui;
 ///     .overlay_text("Progress!")
 ///     .build(&ui);
 /// ```
+///
+/// When the progress can't be measured, an indeterminate progress bar shows a moving bar instead:
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut imgui = Context::create();
+/// # let ui = imgui.frame();
+/// ProgressBar::indeterminate()
+///     .overlay_text("Connecting...")
+///     .build(&ui);
+/// ```
 #[derive(Copy, Clone, Debug)]
 #[must_use]
 pub struct ProgressBar<T = &'static str> {
-    fraction: f32,
+    /// `None` for an indeterminate progress bar.
+    fraction: Option<f32>,
     size: [f32; 2],
     overlay_text: Option<T>,
 }
@@ -34,7 +46,21 @@ impl ProgressBar {
     #[doc(alias = "ProgressBar")]
     pub fn new(fraction: f32) -> Self {
         ProgressBar {
-            fraction,
+            fraction: Some(fraction),
+            size: [-1.0, 0.0],
+            overlay_text: None,
+        }
+    }
+
+    /// Creates an indeterminate progress bar, which shows a bar moving back and forth for
+    /// progress of unknown length.
+    ///
+    /// Like other progress bars, it fills the entire width of the window by default.
+    #[inline]
+    #[doc(alias = "ProgressBar")]
+    pub fn indeterminate() -> Self {
+        ProgressBar {
+            fraction: None,
             size: [-1.0, 0.0],
             overlay_text: None,
         }
@@ -63,9 +89,13 @@ impl<T: AsRef<str>> ProgressBar<T> {
 
     /// Builds the progress bar
     pub fn build(self, ui: &Ui) {
+        // Dear ImGui animates progress bars with a negative fraction, using it as the time.
+        let fraction = self
+            .fraction
+            .unwrap_or(-(ui.time() as f32).max(f32::MIN_POSITIVE));
         unsafe {
             sys::igProgressBar(
-                self.fraction,
+                fraction,
                 self.size.into(),
                 ui.scratch_txt_opt(self.overlay_text),
             );
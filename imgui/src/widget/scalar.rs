@@ -0,0 +1,369 @@
+use std::cmp::Ordering;
+use std::os::raw::c_void;
+
+use crate::internal::DataTypeKind;
+
+/// A value which can be edited by the scalar widgets: [`Drag`](crate::Drag),
+/// [`Slider`](crate::Slider), [`VerticalSlider`](crate::VerticalSlider) and
+/// [`InputScalar`](crate::InputScalar).
+///
+/// This is implemented for all primitive [`DataTypeKind`] types. Other types, such as unit
+/// newtypes or fixed-point numbers, are edited as the primitive `Repr` they convert to: Dear
+/// ImGui formats, parses, steps and clamps the converted value, and the edited value is
+/// converted back.
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// #[derive(Clone, Copy)]
+/// struct Meters(f32);
+///
+/// impl Scalar for Meters {
+///     type Repr = f32;
+///     const FORMAT: Option<&'static str> = Some("%.2f m");
+///
+///     fn to_repr(self) -> f32 {
+///         self.0
+///     }
+///
+///     fn from_repr(repr: f32) -> Self {
+///         Meters(repr)
+///     }
+/// }
+///
+/// /// A 16.16 fixed-point number, edited as a float.
+/// #[derive(Clone, Copy)]
+/// struct Q16(i32);
+///
+/// impl Scalar for Q16 {
+///     type Repr = f64;
+///
+///     fn to_repr(self) -> f64 {
+///         self.0 as f64 / 65536.0
+///     }
+///
+///     fn from_repr(repr: f64) -> Self {
+///         Q16((repr * 65536.0).round() as i32)
+///     }
+/// }
+///
+/// let mut height = Meters(1.8);
+/// let mut gain = Q16(1 << 16);
+/// Drag::new("Height").speed(0.01).build(ui, &mut height);
+/// ui.slider("Gain", Q16(0), Q16(4 << 16), &mut gain);
+/// ```
+pub trait Scalar: Copy {
+    /// The primitive type Dear ImGui edits.
+    type Repr: DataTypeKind;
+
+    /// Display format used when the widget doesn't set one, as a printf string for `Repr`.
+    ///
+    /// Text after the number is ignored when the user types a value, so units can be appended,
+    /// as in `"%.2f m"`. `None` uses Dear ImGui's default format for `Repr`.
+    const FORMAT: Option<&'static str> = None;
+
+    /// Converts the value to the type edited by Dear ImGui.
+    fn to_repr(self) -> Self::Repr;
+
+    /// Converts an edited value back. This can also restrict the value, for example by wrapping
+    /// angles around.
+    fn from_repr(repr: Self::Repr) -> Self;
+}
+
+impl<T: DataTypeKind> Scalar for T {
+    type Repr = T;
+
+    #[inline]
+    fn to_repr(self) -> T {
+        self
+    }
+
+    #[inline]
+    fn from_repr(repr: T) -> T {
+        repr
+    }
+}
+
+/// Edits `value` through a pointer to its `Repr`, converting the result back if `edit` returns
+/// true.
+pub(crate) fn edit_scalar<T: Scalar>(
+    value: &mut T,
+    edit: impl FnOnce(*mut c_void) -> bool,
+) -> bool {
+    let mut repr = value.to_repr();
+    let changed = edit(&mut repr as *mut T::Repr as *mut c_void);
+    if changed {
+        *value = T::from_repr(repr);
+    }
+    changed
+}
+
+/// Returns the display format of a widget, falling back to the format of its scalar type.
+pub(crate) fn scalar_format<T: Scalar>(format: &Option<impl AsRef<str>>) -> Option<&str> {
+    format.as_ref().map(AsRef::as_ref).or(T::FORMAT)
+}
+
+/// A primitive number type, which the widgets computing with their values, such as
+/// [`DragRange`](crate::DragRange), [`SliderRange`](crate::SliderRange) and
+/// [`Knob`](crate::Knob), can edit.
+///
+/// This is implemented for all primitive integer and float types, and can't be implemented
+/// outside of imgui-rs. Use [`Scalar`] to edit other types as a primitive `Repr` where the
+/// widget supports it.
+pub trait Primitive: DataTypeKind + sealed::Arithmetic {}
+
+mod sealed {
+    /// Arithmetic on the primitive types, kept private so that [`Primitive`](super::Primitive)
+    /// can only be implemented here.
+    pub trait Arithmetic: Copy + PartialOrd {
+        const LOWEST: Self;
+        const HIGHEST: Self;
+        fn saturating_add(self, rhs: Self) -> Self;
+        fn saturating_sub(self, rhs: Self) -> Self;
+        fn to_f64(self) -> f64;
+        fn from_f64(value: f64) -> Self;
+        fn display(self, precision: Option<usize>) -> String;
+    }
+}
+
+macro_rules! impl_primitive_int {
+    ($($ty:ty),*) => {
+        $(
+            impl Primitive for $ty {}
+
+            impl sealed::Arithmetic for $ty {
+                const LOWEST: Self = <$ty>::MIN;
+                const HIGHEST: Self = <$ty>::MAX;
+                fn saturating_add(self, rhs: Self) -> Self {
+                    <$ty>::saturating_add(self, rhs)
+                }
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    <$ty>::saturating_sub(self, rhs)
+                }
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+                fn from_f64(value: f64) -> Self {
+                    // `as` saturates at the bounds of the type
+                    value.round() as $ty
+                }
+                fn display(self, _: Option<usize>) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_primitive_float {
+    ($($ty:ty),*) => {
+        $(
+            impl Primitive for $ty {}
+
+            impl sealed::Arithmetic for $ty {
+                const LOWEST: Self = -<$ty>::MAX;
+                const HIGHEST: Self = <$ty>::MAX;
+                fn saturating_add(self, rhs: Self) -> Self {
+                    self + rhs
+                }
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    self - rhs
+                }
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+                fn from_f64(value: f64) -> Self {
+                    value as $ty
+                }
+                fn display(self, precision: Option<usize>) -> String {
+                    format!("{:.*}", precision.unwrap_or(3), self)
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_int!(i8, u8, i16, u16, i32, u32, i64, u64, isize, usize);
+impl_primitive_float!(f32, f64);
+
+/// Returns the lowest value of a data type (`-MAX` for floats).
+pub(crate) fn scalar_lowest<T: Primitive>() -> T {
+    T::LOWEST
+}
+
+/// Returns the highest value of a data type.
+pub(crate) fn scalar_highest<T: Primitive>() -> T {
+    T::HIGHEST
+}
+
+/// Compares two values of a data type.
+pub(crate) fn scalar_cmp<T: Primitive>(a: T, b: T) -> Option<Ordering> {
+    a.partial_cmp(&b)
+}
+
+/// Returns the smaller of two values of a data type.
+pub(crate) fn scalar_min<T: Primitive>(a: T, b: T) -> T {
+    if scalar_cmp(b, a) == Some(Ordering::Less) {
+        b
+    } else {
+        a
+    }
+}
+
+/// Returns the larger of two values of a data type.
+pub(crate) fn scalar_max<T: Primitive>(a: T, b: T) -> T {
+    if scalar_cmp(b, a) == Some(Ordering::Greater) {
+        b
+    } else {
+        a
+    }
+}
+
+/// Clamps `value` to `lower..=upper`, returning true if it changed.
+pub(crate) fn scalar_clamp<T: Primitive>(value: &mut T, lower: T, upper: T) -> bool {
+    if scalar_cmp(*value, lower) == Some(Ordering::Less) {
+        *value = lower;
+        true
+    } else if scalar_cmp(*value, upper) == Some(Ordering::Greater) {
+        *value = upper;
+        true
+    } else {
+        false
+    }
+}
+
+/// Adds two values of a data type, saturating at the bounds of integers.
+pub(crate) fn scalar_add<T: Primitive>(a: T, b: T) -> T {
+    a.saturating_add(b)
+}
+
+/// Subtracts two values of a data type, saturating at the bounds of integers.
+pub(crate) fn scalar_sub<T: Primitive>(a: T, b: T) -> T {
+    a.saturating_sub(b)
+}
+
+/// Converts a value of a data type to `f64`, which may lose precision for 64-bit integers.
+pub(crate) fn scalar_to_f64<T: Primitive>(value: T) -> f64 {
+    value.to_f64()
+}
+
+/// Converts a `f64` to a data type, rounding and saturating integers.
+pub(crate) fn scalar_from_f64<T: Primitive>(value: f64) -> T {
+    T::from_f64(value)
+}
+
+/// Formats a value with a printf-style display format holding at most one conversion, such as
+/// `"%d"`, `"%.2f"` or `"%.1f ms"`. Only the precision of the conversion is used.
+pub(crate) fn scalar_display<T: Primitive>(value: T, format: Option<&str>) -> String {
+    let format = format.unwrap_or("%");
+    let mut prefix = String::new();
+    let mut chars = format.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        if c != '%' {
+            prefix.push(c);
+            continue;
+        }
+        if chars.next_if(|&(_, c)| c == '%').is_some() {
+            prefix.push('%');
+            continue;
+        }
+        // Skip flags and width, read the precision and find the end of the conversion.
+        let spec = &format[index + 1..];
+        let end = spec
+            .find(|c: char| c.is_ascii_alphabetic() && c != 'l' && c != 'h' && c != 'I')
+            .map_or(spec.len(), |end| end + 1);
+        let precision = spec[..end].split_once('.').and_then(|(_, rest)| {
+            let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+            digits.parse().ok()
+        });
+        let number = value.display(precision);
+        return prefix + &number + &spec[end..].replace("%%", "%");
+    }
+    prefix
+}
+
+#[test]
+fn test_custom_scalar_input() {
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Q16(i32);
+
+    impl Scalar for Q16 {
+        type Repr = f64;
+        const FORMAT: Option<&'static str> = Some("%.3f units");
+
+        fn to_repr(self) -> f64 {
+            self.0 as f64 / 65536.0
+        }
+
+        fn from_repr(repr: f64) -> Self {
+            Q16((repr * 65536.0).round() as i32)
+        }
+    }
+
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.io_mut().config_input_trickle_event_queue = false;
+    let mut value = Q16(1 << 16);
+    let mut limited = Q16(1 << 16);
+    for frame in 0..4 {
+        if frame == 2 {
+            "2.5"
+                .chars()
+                .for_each(|c| ctx.io_mut().add_input_character(c));
+        }
+        let ui = ctx.new_frame();
+        ui.window("scalar").build(|| {
+            if frame == 0 {
+                ui.set_keyboard_focus_here();
+            }
+            ui.input_scalar("value", &mut value).build();
+            assert!(!ui.slider("limited", Q16(0), Q16(2 << 16), &mut limited));
+        });
+        ctx.render();
+    }
+    assert_eq!(value, Q16(5 << 15));
+    assert_eq!(limited, Q16(1 << 16));
+}
+
+#[test]
+fn test_scalar_display() {
+    assert_eq!(scalar_display(42u8, None), "42");
+    assert_eq!(scalar_display(-7i64, Some("%lld items")), "-7 items");
+    assert_eq!(scalar_display(1.5f32, None), "1.500");
+    assert_eq!(scalar_display(0.25f64, Some("%.1f%% done")), "0.2% done");
+    assert_eq!(scalar_display(3.0f32, Some("x = %8.2f")), "x = 3.00");
+}
+
+#[test]
+fn test_range_clamping() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.frame();
+    ui.window("ranges").build(|| {
+        let (mut min, mut max) = (50u8, 40u8);
+        let drag = crate::DragRange::new("drag").range(0, 100).min_gap(5);
+        assert!(drag.build(ui, &mut min, &mut max));
+        assert_eq!((min, max), (35, 40));
+
+        let (mut min, mut max) = (-1.0f64, 2.0f64);
+        assert!(ui.slider_range("slider", 0.0, 1.0, &mut min, &mut max));
+        assert_eq!((min, max), (0.0, 1.0));
+        assert!(!ui.slider_range("slider", 0.0, 1.0, &mut min, &mut max));
+
+        let (mut min, mut max) = (7usize, 3usize);
+        let drag = crate::DragRange::new("usize").range(0, 5);
+        assert!(drag.build(ui, &mut min, &mut max));
+        assert_eq!((min, max), (3, 3));
+        let (mut min, mut max) = (2usize, 20usize);
+        assert!(ui.slider_range("usize", 0, 10, &mut min, &mut max));
+        assert_eq!((min, max), (2, 10));
+        let mut values = [1isize, -1];
+        assert!(!ui.mixer_sliders("isize", -2, 2).linked(true).build(&mut values));
+
+        // Empty ranges leave the values unclamped.
+        let (mut min, mut max) = (-3i8, 100i8);
+        let drag = crate::DragRange::new("empty").range(5, 5);
+        assert!(!drag.build(ui, &mut min, &mut max));
+        assert!(!ui.slider_range("empty", 1, 1, &mut min, &mut max));
+        assert_eq!((min, max), (-3, 100));
+    });
+}
@@ -1,9 +1,15 @@
 use bitflags::bitflags;
+use std::cmp::Ordering;
 use std::os::raw::c_void;
 
 use crate::internal::DataTypeKind;
 use crate::math::MintVec2;
+use crate::style::StyleColor;
 use crate::sys;
+use crate::widget::scalar::{
+    edit_scalar, scalar_add, scalar_clamp, scalar_cmp, scalar_display, scalar_format,
+    scalar_from_f64, scalar_highest, scalar_lowest, scalar_sub, scalar_to_f64, Primitive, Scalar,
+};
 use crate::Ui;
 
 bitflags!(
@@ -25,7 +31,7 @@ bitflags!(
 
 impl Ui {
     /// Creates a new slider widget. Returns true if the value has been edited.
-    pub fn slider<T: AsRef<str>, K: DataTypeKind>(
+    pub fn slider<T: AsRef<str>, K: Scalar>(
         &self,
         label: T,
         min: K,
@@ -36,7 +42,7 @@ impl Ui {
     }
 
     /// Creates an new ubuilt Slider.
-    pub fn slider_config<T: AsRef<str>, K: DataTypeKind>(
+    pub fn slider_config<T: AsRef<str>, K: Scalar>(
         &self,
         label: T,
         min: K,
@@ -51,6 +57,114 @@ impl Ui {
             ui: self,
         }
     }
+
+    /// Creates a slider with two handles, editing the `min` and `max` ends of a range within
+    /// `lower..=upper`. Returns true if either value has been edited.
+    pub fn slider_range<T: AsRef<str>, K: Primitive>(
+        &self,
+        label: T,
+        lower: K,
+        upper: K,
+        min: &mut K,
+        max: &mut K,
+    ) -> bool {
+        self.slider_range_config(label, lower, upper)
+            .build(min, max)
+    }
+
+    /// Creates an unbuilt two handle slider, see [`Ui::slider_range`].
+    pub fn slider_range_config<T: AsRef<str>, K: Primitive>(
+        &self,
+        label: T,
+        lower: K,
+        upper: K,
+    ) -> SliderRange<'_, T, K> {
+        SliderRange {
+            label,
+            lower,
+            upper,
+            min_gap: None,
+            display_format: Option::<&'static str>::None,
+            ui: self,
+        }
+    }
+
+    /// Creates a new vertical slider widget. Returns true if the value has been edited.
+    #[doc(alias = "VSliderScalar")]
+    pub fn vertical_slider<T: AsRef<str>, K: Scalar>(
+        &self,
+        label: T,
+        size: impl Into<MintVec2>,
+        min: K,
+        max: K,
+        value: &mut K,
+    ) -> bool {
+        self.vertical_slider_config(label, size, min, max)
+            .build(value)
+    }
+
+    /// Creates an unbuilt vertical slider of the given size.
+    pub fn vertical_slider_config<T: AsRef<str>, K: Scalar>(
+        &self,
+        label: T,
+        size: impl Into<MintVec2>,
+        min: K,
+        max: K,
+    ) -> VerticalSlider<'_, T, K> {
+        VerticalSlider {
+            label,
+            size: size.into().into(),
+            min,
+            max,
+            display_format: None,
+            flags: SliderFlags::empty(),
+            ui: self,
+        }
+    }
+
+    /// Creates a new angle slider widget, editing a value in radians between -360 and 360
+    /// degrees. Returns true if the value has been edited.
+    #[doc(alias = "SliderAngle")]
+    pub fn angle_slider<T: AsRef<str>>(&self, label: T, value_rad: &mut f32) -> bool {
+        self.angle_slider_config(label).build(value_rad)
+    }
+
+    /// Creates an unbuilt angle slider, where its minimum defaults to -360.0 and maximum
+    /// defaults to 360.0 degrees.
+    pub fn angle_slider_config<T: AsRef<str>>(&self, label: T) -> AngleSlider<'_, T> {
+        AngleSlider {
+            label,
+            min_degrees: -360.0,
+            max_degrees: 360.0,
+            display_format: "%.0f deg",
+            flags: SliderFlags::empty(),
+            ui: self,
+        }
+    }
+
+    /// Creates an unbuilt row of vertical sliders sharing the given range, see
+    /// [`MixerSliders`].
+    pub fn mixer_sliders<T: AsRef<str>, K: Scalar>(
+        &self,
+        label: T,
+        min: K,
+        max: K,
+    ) -> MixerSliders<'_, T, K>
+    where
+        K::Repr: Primitive,
+    {
+        MixerSliders {
+            label,
+            min,
+            max,
+            size: None,
+            channel_labels: &[],
+            linked: false,
+            display_format: None,
+            flags: SliderFlags::empty(),
+            ui: self,
+        }
+    }
 }
 
 /// Builder for a slider widget.
@@ -65,7 +179,7 @@ pub struct Slider<'ui, Label, Data, Format = &'static str> {
     ui: &'ui Ui,
 }
 
-impl<'ui, T: AsRef<str>, K: DataTypeKind> Slider<'ui, T, K> {
+impl<'ui, T: AsRef<str>, K: Scalar> Slider<'ui, T, K> {
     /// Constructs a new slider builder with the given range.
     #[doc(alias = "SliderScalar", alias = "SliderScalarN")]
     #[deprecated(note = "Use `Ui::slider` or `Ui::slider_config`.", since = "0.9.0")]
@@ -84,7 +198,7 @@ impl<'ui, T: AsRef<str>, K: DataTypeKind> Slider<'ui, T, K> {
 impl<'ui, Label, Data, Format> Slider<'ui, Label, Data, Format>
 where
     Label: AsRef<str>,
-    Data: DataTypeKind,
+    Data: Scalar,
     Format: AsRef<str>,
 {
     /// Sets the range inclusively, such that both values given
@@ -137,22 +251,30 @@ where
     ///
     /// Returns true if the slider value was changed.
     pub fn build(self, value: &mut Data) -> bool {
-        unsafe {
-            let (label, display_format) = self
-                .ui
-                .scratch_txt_with_opt(self.label, self.display_format);
-
+        let (min, max) = (self.min.to_repr(), self.max.to_repr());
+        let (label, display_format) = self
+            .ui
+            .scratch_txt_with_opt(&self.label, scalar_format::<Data>(&self.display_format));
+        edit_scalar(value, |value| unsafe {
             sys::igSliderScalar(
                 label,
-                Data::KIND as i32,
-                value as *mut Data as *mut c_void,
-                &self.min as *const Data as *const c_void,
-                &self.max as *const Data as *const c_void,
+                Data::Repr::KIND as i32,
+                value,
+                &min as *const Data::Repr as *const c_void,
+                &max as *const Data::Repr as *const c_void,
                 display_format,
                 self.flags.bits() as i32,
             )
-        }
+        })
     }
+}
+
+impl<'ui, Label, Data, Format> Slider<'ui, Label, Data, Format>
+where
+    Label: AsRef<str>,
+    Data: DataTypeKind,
+    Format: AsRef<str>,
+{
     /// Builds a horizontal array of multiple sliders attached to the given slice.
     ///
     /// Returns true if any slider value was changed.
@@ -176,59 +298,236 @@ where
     }
 }
 
+/// Builder for a slider with two handles, drawn as a single bar with the selected range
+/// highlighted between the handles.
+#[derive(Copy, Clone, Debug)]
+#[must_use]
+pub struct SliderRange<'ui, Label, Data, Format = &'static str> {
+    label: Label,
+    lower: Data,
+    upper: Data,
+    min_gap: Option<Data>,
+    display_format: Option<Format>,
+    ui: &'ui Ui,
+}
+
+impl<'ui, Label, Data, Format> SliderRange<'ui, Label, Data, Format>
+where
+    Label: AsRef<str>,
+    Data: Primitive,
+    Format: AsRef<str>,
+{
+    /// Sets the range (inclusive) the handles can be dragged in.
+    ///
+    /// An empty range, where `lower` is not less than `upper`, leaves the values unclamped and
+    /// the handles can't be dragged.
+    #[inline]
+    pub fn range(mut self, lower: Data, upper: Data) -> Self {
+        self.lower = lower;
+        self.upper = upper;
+        self
+    }
+    /// Sets the smallest allowed difference between the max and min values.
+    ///
+    /// Defaults to no gap, allowing both handles at the same value.
+    #[inline]
+    pub fn min_gap(mut self, min_gap: Data) -> Self {
+        self.min_gap = Some(min_gap);
+        self
+    }
+    /// Sets the display format using *a C-style printf string*.
+    ///
+    /// Only the precision of the conversion is used, as in `"%.2f s"`.
+    #[inline]
+    pub fn display_format<Format2: AsRef<str>>(
+        self,
+        display_format: Format2,
+    ) -> SliderRange<'ui, Label, Data, Format2> {
+        SliderRange {
+            label: self.label,
+            lower: self.lower,
+            upper: self.upper,
+            min_gap: self.min_gap,
+            display_format: Some(display_format),
+            ui: self.ui,
+        }
+    }
+    /// Builds a two handle slider bound to the given min/max values.
+    ///
+    /// Clicking the bar drags the nearest handle. Both values are clamped to the range and kept
+    /// on their side of the other value.
+    ///
+    /// This is drawn in Rust rather than by Dear ImGui, so it only responds to the mouse: there
+    /// is no keyboard or gamepad navigation and no Ctrl+click text input.
+    ///
+    /// Returns true if either value was changed.
+    pub fn build(self, min: &mut Data, max: &mut Data) -> bool {
+        let ui = self.ui;
+        let label = self.label.as_ref();
+        let style = ui.clone_style();
+        let (lower, upper) = (self.lower, self.upper);
+        let gap = self.min_gap;
+        let with_gap =
+            |value: Data, apply: fn(Data, Data) -> Data| gap.map_or(value, |gap| apply(value, gap));
+
+        let _id = ui.push_id(label);
+        let _group = ui.begin_group();
+        let pos = ui.cursor_screen_pos();
+        let size = [ui.calc_item_width(), ui.frame_height()];
+        let handle_id = ui.new_id_str("##handle");
+        ui.invisible_button("##range", size);
+        let (hovered, active) = (ui.is_item_hovered(), ui.is_item_active());
+
+        // Pixel positions of the lower and upper ends of the range.
+        let padding = style.frame_border_size + 2.0;
+        let grab_width = style.grab_min_size.min(size[0] / 4.0);
+        let start = pos[0] + padding + grab_width / 2.0;
+        let end = pos[0] + size[0] - padding - grab_width / 2.0;
+        let span = scalar_to_f64(upper) - scalar_to_f64(lower);
+        let to_x = |value: Data| {
+            let t = if span > 0.0 {
+                ((scalar_to_f64(value) - scalar_to_f64(lower)) / span).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            start + (end - start) * t as f32
+        };
+
+        // An empty range leaves the values unclamped, as Dear ImGui does for drags.
+        let (lower, upper) = if scalar_cmp(lower, upper) == Some(Ordering::Less) {
+            (lower, upper)
+        } else {
+            (scalar_lowest(), scalar_highest())
+        };
+        let mut changed = scalar_clamp(min, lower, with_gap(*max, scalar_sub));
+        changed |= scalar_clamp(max, with_gap(*min, scalar_add), upper);
+        let mouse_x = ui.io().mouse_pos[0];
+        let mut storage = ui.state_storage();
+        if ui.is_item_activated() {
+            // Drag the nearest handle, or the one on the side of the click if they overlap.
+            let (min_x, max_x) = (to_x(*min), to_x(*max));
+            let drag_max = if min_x == max_x {
+                mouse_x > max_x
+            } else {
+                (mouse_x - max_x).abs() < (mouse_x - min_x).abs()
+            };
+            storage.set_bool(handle_id, drag_max);
+        }
+        let drag_max = storage.get_bool(handle_id, false);
+        if active && end > start && span > 0.0 {
+            let t = ((mouse_x - start) / (end - start)).clamp(0.0, 1.0) as f64;
+            let value = scalar_from_f64(scalar_to_f64(self.lower) + span * t);
+            let previous = if drag_max { *max } else { *min };
+            if drag_max {
+                *max = value;
+                scalar_clamp(max, with_gap(*min, scalar_add), upper);
+            } else {
+                *min = value;
+                scalar_clamp(min, lower, with_gap(*max, scalar_sub));
+            }
+            let current = if drag_max { *max } else { *min };
+            changed |= scalar_cmp(previous, current) != Some(Ordering::Equal);
+        }
+
+        let draw_list = ui.get_window_draw_list();
+        let frame_color = if active {
+            StyleColor::FrameBgActive
+        } else if hovered {
+            StyleColor::FrameBgHovered
+        } else {
+            StyleColor::FrameBg
+        };
+        let frame_end = [pos[0] + size[0], pos[1] + size[1]];
+        draw_list
+            .add_rect(pos, frame_end, ui.style_color_u32(frame_color, 1.0))
+            .filled(true)
+            .rounding(style.frame_rounding)
+            .build();
+        let (top, bottom) = (pos[1] + padding, frame_end[1] - padding);
+        let (min_x, max_x) = (to_x(*min), to_x(*max));
+        let range_color = ui.style_color_u32(StyleColor::SliderGrab, 0.4);
+        draw_list
+            .add_rect([min_x, top], [max_x, bottom], range_color)
+            .filled(true)
+            .build();
+        for (x, is_max) in [(min_x, false), (max_x, true)] {
+            let grab_color = if active && drag_max == is_max {
+                StyleColor::SliderGrabActive
+            } else {
+                StyleColor::SliderGrab
+            };
+            draw_list
+                .add_rect(
+                    [x - grab_width / 2.0, top],
+                    [x + grab_width / 2.0, bottom],
+                    ui.style_color_u32(grab_color, 1.0),
+                )
+                .filled(true)
+                .rounding(style.grab_rounding)
+                .build();
+        }
+        let format = self.display_format.as_ref().map(AsRef::as_ref);
+        let text = format!(
+            "{} - {}",
+            scalar_display(*min, format),
+            scalar_display(*max, format)
+        );
+        let text_size = ui.calc_text_size(&text);
+        let text_pos = [
+            pos[0] + (size[0] - text_size[0]) / 2.0,
+            pos[1] + (size[1] - text_size[1]) / 2.0,
+        ];
+        draw_list.add_text(text_pos, ui.style_color_u32(StyleColor::Text, 1.0), text);
+        drop(draw_list);
+
+        let visible = label.split("##").next().unwrap_or_default();
+        if !visible.is_empty() {
+            ui.same_line_with_spacing(0.0, style.item_inner_spacing[0]);
+            ui.text(visible);
+        }
+        changed
+    }
+}
+
 /// Builder for a vertical slider widget.
 #[derive(Clone, Debug)]
 #[must_use]
-pub struct VerticalSlider<Label, Data, Format = &'static str> {
+pub struct VerticalSlider<'ui, Label, Data, Format = &'static str> {
     label: Label,
     size: [f32; 2],
     min: Data,
     max: Data,
     display_format: Option<Format>,
     flags: SliderFlags,
+    ui: &'ui Ui,
 }
 
-impl<Label, Data> VerticalSlider<Label, Data>
-where
-    Label: AsRef<str>,
-    Data: DataTypeKind,
-{
+impl<'ui, T: AsRef<str>, K: Scalar> VerticalSlider<'ui, T, K> {
     /// Constructs a new vertical slider builder with the given size and range.
-    ///
-    /// ```rust
-    /// imgui::VerticalSlider::new("Example", [20.0, 20.0], i8::MIN, i8::MAX)
-    ///     .range(4, 8)
-    ///     // Remember to call .build(&ui)
-    ///     ;
-    /// ```
-    ///
-    /// It is safe, though up to C++ Dear ImGui, on how to handle when
-    /// `min > max`.
     #[doc(alias = "VSliderScalar")]
-    pub fn new(label: Label, size: impl Into<MintVec2>, min: Data, max: Data) -> Self {
-        VerticalSlider {
-            label,
-            size: size.into().into(),
-            min,
-            max,
-            display_format: None,
-            flags: SliderFlags::empty(),
-        }
+    #[deprecated(
+        note = "Use `Ui::vertical_slider` or `Ui::vertical_slider_config`.",
+        since = "0.13.0"
+    )]
+    pub fn new(ui: &'ui Ui, label: T, size: impl Into<MintVec2>, min: K, max: K) -> Self {
+        ui.vertical_slider_config(label, size, min, max)
     }
 }
 
-impl<Label, Data, Format> VerticalSlider<Label, Data, Format>
+impl<'ui, Label, Data, Format> VerticalSlider<'ui, Label, Data, Format>
 where
     Label: AsRef<str>,
-    Data: DataTypeKind,
+    Data: Scalar,
     Format: AsRef<str>,
 {
     /// Sets the range for the vertical slider.
     ///
-    /// ```rust
-    /// imgui::VerticalSlider::new("Example", [20.0, 20.0], i8::MIN, i8::MAX)
+    /// ```no_run
+    /// # let mut ctx = imgui::Context::create();
+    /// # let ui = ctx.frame();
+    /// ui.vertical_slider_config("Example", [20.0, 20.0], i8::MIN, i8::MAX)
     ///     .range(4, 8)
-    ///     // Remember to call .build(&ui)
+    ///     // Remember to call .build()
     ///     ;
     /// ```
     ///
@@ -245,7 +544,7 @@ where
     pub fn display_format<Format2: AsRef<str>>(
         self,
         display_format: Format2,
-    ) -> VerticalSlider<Label, Data, Format2> {
+    ) -> VerticalSlider<'ui, Label, Data, Format2> {
         VerticalSlider {
             label: self.label,
             size: self.size,
@@ -253,6 +552,7 @@ where
             max: self.max,
             display_format: Some(display_format),
             flags: self.flags,
+            ui: self.ui,
         }
     }
     /// Replaces all current settings with the given flags
@@ -264,63 +564,87 @@ where
     /// Builds a vertical slider that is bound to the given value.
     ///
     /// Returns true if the slider value was changed.
-    pub fn build(self, ui: &Ui, value: &mut Data) -> bool {
-        unsafe {
-            let (label, display_format) = ui.scratch_txt_with_opt(self.label, self.display_format);
-
+    pub fn build(self, value: &mut Data) -> bool {
+        let (min, max) = (self.min.to_repr(), self.max.to_repr());
+        let (label, display_format) = self
+            .ui
+            .scratch_txt_with_opt(&self.label, scalar_format::<Data>(&self.display_format));
+        edit_scalar(value, |value| unsafe {
             sys::igVSliderScalar(
                 label,
                 self.size.into(),
-                Data::KIND as i32,
-                value as *mut Data as *mut c_void,
-                &self.min as *const Data as *const c_void,
-                &self.max as *const Data as *const c_void,
+                Data::Repr::KIND as i32,
+                value,
+                &min as *const Data::Repr as *const c_void,
+                &max as *const Data::Repr as *const c_void,
                 display_format,
                 self.flags.bits() as i32,
             )
-        }
+        })
+    }
+    /// Builds a horizontal row of vertical sliders, one for each value of the given slice, each
+    /// with the size of the slider.
+    ///
+    /// Returns true if any slider value was changed.
+    pub fn build_array(self, values: &mut [Data]) -> bool {
+        let VerticalSlider {
+            label,
+            size,
+            min,
+            max,
+            display_format,
+            flags,
+            ui,
+        } = self;
+        build_components(ui, label.as_ref(), values, None, |_, value| {
+            let slider = ui
+                .vertical_slider_config("##v", size, min, max)
+                .flags(flags);
+            match &display_format {
+                Some(format) => slider.display_format(format).build(value),
+                None => slider.build(value),
+            }
+        })
     }
 }
 
 /// Builder for an angle slider widget.
 #[derive(Copy, Clone, Debug)]
 #[must_use]
-pub struct AngleSlider<Label, Format = &'static str> {
+pub struct AngleSlider<'ui, Label, Format = &'static str> {
     label: Label,
     min_degrees: f32,
     max_degrees: f32,
     display_format: Format,
     flags: SliderFlags,
+    ui: &'ui Ui,
 }
 
-impl<Label> AngleSlider<Label>
-where
-    Label: AsRef<str>,
-{
+impl<'ui, T: AsRef<str>> AngleSlider<'ui, T> {
     /// Constructs a new angle slider builder, where its minimum defaults to -360.0 and
     /// maximum defaults to 360.0
     #[doc(alias = "SliderAngle")]
-    pub fn new(label: Label) -> Self {
-        AngleSlider {
-            label,
-            min_degrees: -360.0,
-            max_degrees: 360.0,
-            display_format: "%.0f deg",
-            flags: SliderFlags::empty(),
-        }
+    #[deprecated(
+        note = "Use `Ui::angle_slider` or `Ui::angle_slider_config`.",
+        since = "0.13.0"
+    )]
+    pub fn new(ui: &'ui Ui, label: T) -> Self {
+        ui.angle_slider_config(label)
     }
 }
 
-impl<Label, Format> AngleSlider<Label, Format>
+impl<'ui, Label, Format> AngleSlider<'ui, Label, Format>
 where
     Label: AsRef<str>,
     Format: AsRef<str>,
 {
     /// Sets the range in degrees (inclusive)
-    /// ```rust
-    /// imgui::AngleSlider::new("Example")
+    /// ```no_run
+    /// # let mut ctx = imgui::Context::create();
+    /// # let ui = ctx.frame();
+    /// ui.angle_slider_config("Example")
     ///     .range_degrees(-20.0, 20.0)
-    ///     // Remember to call .build(&ui)
+    ///     // Remember to call .build()
     ///     ;
     /// ```
     ///
@@ -349,13 +673,14 @@ where
     pub fn display_format<Format2: AsRef<str>>(
         self,
         display_format: Format2,
-    ) -> AngleSlider<Label, Format2> {
+    ) -> AngleSlider<'ui, Label, Format2> {
         AngleSlider {
             label: self.label,
             min_degrees: self.min_degrees,
             max_degrees: self.max_degrees,
             display_format,
             flags: self.flags,
+            ui: self.ui,
         }
     }
     /// Replaces all current settings with the given flags
@@ -367,9 +692,9 @@ where
     /// Builds an angle slider that is bound to the given value (in radians).
     ///
     /// Returns true if the slider value was changed.
-    pub fn build(self, ui: &Ui, value_rad: &mut f32) -> bool {
+    pub fn build(self, value_rad: &mut f32) -> bool {
         unsafe {
-            let (label, display_format) = ui.scratch_txt_two(self.label, self.display_format);
+            let (label, display_format) = self.ui.scratch_txt_two(self.label, self.display_format);
 
             sys::igSliderAngle(
                 label,
@@ -381,4 +706,266 @@ where
             )
         }
     }
+    /// Builds a horizontal array of angle sliders attached to the given slice of values (in
+    /// radians), sharing the item width.
+    ///
+    /// Returns true if any slider value was changed.
+    pub fn build_array(self, values_rad: &mut [f32]) -> bool {
+        let AngleSlider {
+            label,
+            min_degrees,
+            max_degrees,
+            display_format,
+            flags,
+            ui,
+        } = self;
+        let width = ui.calc_item_width();
+        build_components(
+            ui,
+            label.as_ref(),
+            values_rad,
+            Some(width),
+            |_, value_rad| {
+                ui.angle_slider_config("##v")
+                    .range_degrees(min_degrees, max_degrees)
+                    .display_format(&display_format)
+                    .flags(flags)
+                    .build(value_rad)
+            },
+        )
+    }
+}
+
+/// Builds one widget per value on a single line, followed by the visible part of the label,
+/// like Dear ImGui's `SliderScalarN`. If `width` is given, it is split between the widgets.
+fn build_components<T>(
+    ui: &Ui,
+    label: &str,
+    values: &mut [T],
+    width: Option<f32>,
+    mut build: impl FnMut(usize, &mut T) -> bool,
+) -> bool {
+    let spacing = ui.clone_style().item_inner_spacing[0];
+    let count = values.len().max(1) as f32;
+    let item_width = width.map(|width| ((width - spacing * (count - 1.0)) / count).max(1.0));
+
+    let _group = ui.begin_group();
+    let _id = ui.push_id(label);
+    let mut changed = false;
+    for (index, value) in values.iter_mut().enumerate() {
+        let _id = ui.push_id_usize(index);
+        if index > 0 {
+            ui.same_line_with_spacing(0.0, spacing);
+        }
+        if let Some(item_width) = item_width {
+            ui.set_next_item_width(item_width);
+        }
+        changed |= build(index, value);
+    }
+    let visible = label.split("##").next().unwrap_or_default();
+    if !visible.is_empty() {
+        ui.same_line_with_spacing(0.0, spacing);
+        ui.text(visible);
+    }
+    changed
+}
+
+/// Builder for a row of vertical sliders sharing a range, like the channel faders of an audio
+/// mixer.
+///
+/// ```no_run
+/// # let mut ctx = imgui::Context::create();
+/// # let ui = ctx.frame();
+/// let mut volumes = [0.8f32, 0.5, 0.65];
+/// ui.mixer_sliders("Mixer", 0.0, 1.0)
+///     .channel_labels(&["L", "R", "Sub"])
+///     .linked(true)
+///     .build(&mut volumes);
+/// ```
+#[derive(Copy, Clone, Debug)]
+#[must_use]
+pub struct MixerSliders<'ui, Label, Data, Channel = &'static str, Format = &'static str> {
+    label: Label,
+    min: Data,
+    max: Data,
+    size: Option<[f32; 2]>,
+    channel_labels: &'ui [Channel],
+    linked: bool,
+    display_format: Option<Format>,
+    flags: SliderFlags,
+    ui: &'ui Ui,
+}
+
+impl<'ui, Label, Data, Channel, Format> MixerSliders<'ui, Label, Data, Channel, Format>
+where
+    Label: AsRef<str>,
+    Data: Scalar,
+    Data::Repr: Primitive,
+    Channel: AsRef<str>,
+    Format: AsRef<str>,
+{
+    /// Sets the range (inclusive) shared by all the channels.
+    #[inline]
+    pub fn range(mut self, min: Data, max: Data) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+    /// Sets the size of each channel's slider.
+    ///
+    /// Defaults to two frame heights wide and 8 frame heights tall.
+    #[inline]
+    pub fn size(mut self, size: impl Into<MintVec2>) -> Self {
+        self.size = Some(size.into().into());
+        self
+    }
+    /// Sets the labels shown under the channels, in order. Channels without a label show none.
+    #[inline]
+    pub fn channel_labels<Channel2: AsRef<str>>(
+        self,
+        channel_labels: &'ui [Channel2],
+    ) -> MixerSliders<'ui, Label, Data, Channel2, Format> {
+        MixerSliders {
+            label: self.label,
+            min: self.min,
+            max: self.max,
+            size: self.size,
+            channel_labels,
+            linked: self.linked,
+            display_format: self.display_format,
+            flags: self.flags,
+            ui: self.ui,
+        }
+    }
+    /// When enabled, dragging one channel moves every channel by the same amount, clamped to
+    /// the range.
+    ///
+    /// The amount is computed on the `Repr` of the values, so integer channels move by exact
+    /// steps even for `i64` and `u64`.
+    ///
+    /// Disabled by default.
+    #[inline]
+    pub fn linked(mut self, linked: bool) -> Self {
+        self.linked = linked;
+        self
+    }
+    /// Sets the display format using *a C-style printf string*
+    #[inline]
+    pub fn display_format<Format2: AsRef<str>>(
+        self,
+        display_format: Format2,
+    ) -> MixerSliders<'ui, Label, Data, Channel, Format2> {
+        MixerSliders {
+            label: self.label,
+            min: self.min,
+            max: self.max,
+            size: self.size,
+            channel_labels: self.channel_labels,
+            linked: self.linked,
+            display_format: Some(display_format),
+            flags: self.flags,
+            ui: self.ui,
+        }
+    }
+    /// Replaces all current settings with the given flags
+    #[inline]
+    pub fn flags(mut self, flags: SliderFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+    /// Builds a slider for every channel of the given slice.
+    ///
+    /// Returns true if any channel was changed.
+    pub fn build(self, values: &mut [Data]) -> bool {
+        let ui = self.ui;
+        let frame_height = ui.frame_height();
+        let size = self
+            .size
+            .unwrap_or([frame_height * 2.0, frame_height * 8.0]);
+        let (min, max) = (self.min, self.max);
+        let (lower, upper) = (min.to_repr(), max.to_repr());
+        let (lower, upper) = if scalar_cmp(upper, lower) == Some(Ordering::Less) {
+            (upper, lower)
+        } else {
+            (lower, upper)
+        };
+
+        let mut moved = None;
+        let changed = build_components(ui, self.label.as_ref(), values, None, |index, value| {
+            let _group = ui.begin_group();
+            let previous = *value;
+            let slider = ui
+                .vertical_slider_config("##v", size, min, max)
+                .flags(self.flags);
+            let changed = match &self.display_format {
+                Some(format) => slider.display_format(format).build(value),
+                None => slider.build(value),
+            };
+            if changed {
+                moved = Some((index, previous.to_repr(), value.to_repr()));
+            }
+            if let Some(channel) = self.channel_labels.get(index) {
+                let channel = channel.as_ref();
+                let offset = (size[0] - ui.calc_text_size(channel)[0]).max(0.0) / 2.0;
+                let [x, y] = ui.cursor_pos();
+                ui.set_cursor_pos([x + offset, y]);
+                ui.text(channel);
+            }
+            changed
+        });
+
+        if let (true, Some((dragged, previous, current))) = (self.linked, moved) {
+            // Apply the delta with the operation matching its sign, so unsigned values can move
+            // down.
+            let raised = scalar_cmp(current, previous) == Some(Ordering::Greater);
+            for (index, value) in values.iter_mut().enumerate() {
+                if index != dragged {
+                    let mut repr = if raised {
+                        scalar_add(value.to_repr(), scalar_sub(current, previous))
+                    } else {
+                        scalar_sub(value.to_repr(), scalar_sub(previous, current))
+                    };
+                    scalar_clamp(&mut repr, lower, upper);
+                    *value = Data::from_repr(repr);
+                }
+            }
+        }
+        changed
+    }
+}
+
+#[test]
+fn test_linked_mixer_sliders() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.io_mut().config_input_trickle_event_queue = false;
+    let mut volumes = [10i32, 20, 90];
+    let mut angles = [0.0f32; 3];
+    for frame in 0..5 {
+        // Hover the middle of the first channel, past the window padding of 8 pixels, before
+        // pressing the button.
+        if frame == 0 {
+            ctx.io_mut().add_mouse_pos_event([18.0, 58.0]);
+        }
+        if frame == 2 {
+            ctx.io_mut()
+                .add_mouse_button_event(crate::MouseButton::Left, true);
+        }
+        let ui = ctx.new_frame();
+        ui.window("mixer")
+            .position([0.0, 0.0], crate::Condition::Always)
+            .size([400.0, 300.0], crate::Condition::Always)
+            .no_decoration()
+            .build(|| {
+                ui.mixer_sliders("##mixer", 0, 100)
+                    .size([20.0, 100.0])
+                    .channel_labels(&["a", "b", "c"])
+                    .linked(true)
+                    .build(&mut volumes);
+                assert!(!ui.angle_slider_config("angles").build_array(&mut angles));
+            });
+        ctx.render();
+    }
+    assert!((40..=60).contains(&volumes[0]), "{:?}", volumes);
+    assert_eq!(volumes[1], volumes[0] + 10);
+    assert_eq!(volumes[2], 100);
 }
@@ -0,0 +1,140 @@
+use std::f32::consts::{PI, TAU};
+
+use crate::style::StyleColor;
+use crate::Ui;
+
+impl Ui {
+    /// Creates a busy spinner followed by its label, see [`Spinner`].
+    pub fn spinner(&self, label: impl AsRef<str>) {
+        self.spinner_config(label).build()
+    }
+
+    /// Creates an unbuilt busy spinner.
+    pub fn spinner_config<T: AsRef<str>>(&self, label: T) -> Spinner<'_, T> {
+        Spinner {
+            label,
+            radius: None,
+            thickness: None,
+            color: StyleColor::PlotHistogram,
+            speed: 1.0,
+            ui: self,
+        }
+    }
+}
+
+/// Builder for a busy spinner: an arc turning around a circle, showing that something is in
+/// progress for an unknown time.
+///
+/// Only the visible part of the label is shown, after the spinner. Like the built-in widgets,
+/// the spinner is faded inside [`Ui::begin_disabled`].
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// ui.spinner_config("Loading...")
+///     .color(StyleColor::Text)
+///     .speed(0.5)
+///     .build();
+/// ```
+#[derive(Copy, Clone, Debug)]
+#[must_use]
+pub struct Spinner<'ui, Label> {
+    label: Label,
+    radius: Option<f32>,
+    thickness: Option<f32>,
+    color: StyleColor,
+    speed: f32,
+    ui: &'ui Ui,
+}
+
+impl<'ui, Label: AsRef<str>> Spinner<'ui, Label> {
+    /// Sets the radius of the spinner.
+    ///
+    /// Defaults to half the text line height, so the spinner lines up with text.
+    #[inline]
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+    /// Sets the thickness of the arc.
+    ///
+    /// Defaults to a quarter of the radius.
+    #[inline]
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = Some(thickness);
+        self
+    }
+    /// Sets the style color of the arc.
+    ///
+    /// Defaults to [`StyleColor::PlotHistogram`], the color of the filled part of progress bars.
+    #[inline]
+    pub fn color(mut self, color: StyleColor) -> Self {
+        self.color = color;
+        self
+    }
+    /// Sets the speed of the spinner, in turns per second.
+    ///
+    /// Defaults to 1.0.
+    #[inline]
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+    /// Builds the spinner.
+    pub fn build(self) {
+        let ui = self.ui;
+        let radius = self.radius.unwrap_or_else(|| ui.text_line_height() / 2.0);
+        let thickness = self.thickness.unwrap_or((radius / 4.0).max(1.0));
+        let pos = ui.cursor_screen_pos();
+        ui.dummy([radius * 2.0, radius * 2.0]);
+
+        if ui.is_item_visible() {
+            let center = [pos[0] + radius, pos[1] + radius];
+            // The arc turns while growing and shrinking between a quarter and three quarters
+            // of a turn.
+            let time = ui.time() as f32 * self.speed;
+            let start = time * TAU;
+            let sweep = TAU * (0.5 + 0.25 * (time * PI).sin());
+            let arc_radius = radius - thickness / 2.0;
+            let segments = 24;
+            let points = (0..=segments)
+                .map(|segment| {
+                    let angle = start + sweep * segment as f32 / segments as f32;
+                    [
+                        center[0] + arc_radius * angle.cos(),
+                        center[1] + arc_radius * angle.sin(),
+                    ]
+                })
+                .collect();
+            ui.get_window_draw_list()
+                .add_polyline(points, ui.style_color_u32(self.color, 1.0))
+                .thickness(thickness)
+                .build();
+        }
+
+        let label = self.label.as_ref();
+        let visible = label.split("##").next().unwrap_or_default();
+        if !visible.is_empty() {
+            let spacing = ui.clone_style().item_inner_spacing[0];
+            ui.same_line_with_spacing(0.0, spacing);
+            ui.text(visible);
+        }
+    }
+}
+
+#[test]
+fn test_busy_indicators() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    for disabled in [false, true] {
+        let ui = ctx.new_frame();
+        ui.window("busy").build(|| {
+            let _disabled = ui.begin_disabled(disabled);
+            let faded = ui.style_color_u32(StyleColor::Text, 1.0).to_rgba_f32s()[3];
+            assert_eq!(faded < 1.0, disabled);
+            ui.spinner("Loading");
+            crate::ProgressBar::indeterminate().build(ui);
+        });
+        ctx.render();
+    }
+}
@@ -18,10 +18,28 @@
 //!
 //! See `test_window_impl.rs` for a more complicated example.
 use crate::sys;
-use crate::Ui;
+use crate::{Id, Ui};
 use bitflags::bitflags;
+use std::cell::RefCell;
 use std::ptr;
 
+thread_local! {
+    // Ids of the tab bars currently being built and of their selected tab, innermost last.
+    static TAB_BARS: RefCell<Vec<(Id, Option<Id>)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Ends the innermost tab bar, and records its selected tab for [`Ui::selected_tab_id`].
+unsafe fn end_tab_bar() {
+    if let Some((bar_id, tab_id)) = TAB_BARS.with(|bars| bars.borrow_mut().pop()) {
+        crate::storage::with_item_state(bar_id, |selected: &mut SelectedTab| selected.0 = tab_id);
+    }
+    sys::igEndTabBar()
+}
+
+/// The tab last selected in a tab bar, see [`Ui::selected_tab_id`].
+#[derive(Default)]
+struct SelectedTab(Option<Id>);
+
 bitflags! {
     #[repr(transparent)]
     pub struct TabBarFlags: u32 {
@@ -106,7 +124,7 @@ create_token!(
     pub struct TabBarToken<'ui>;
 
     /// Ends a tab bar.
-    drop { sys::igEndTabBar() }
+    drop { end_tab_bar() }
 );
 
 pub struct TabItem<'a, T> {
@@ -181,10 +199,12 @@ impl Ui {
         id: impl AsRef<str>,
         flags: TabBarFlags,
     ) -> Option<TabBarToken<'_>> {
-        let should_render =
-            unsafe { sys::igBeginTabBar(self.scratch_txt(id), flags.bits() as i32) };
+        let id = self.scratch_txt(id);
+        let bar_id = Id(unsafe { sys::igGetID_Str(id) });
+        let should_render = unsafe { sys::igBeginTabBar(id, flags.bits() as i32) };
 
         if should_render {
+            TAB_BARS.with(|bars| bars.borrow_mut().push((bar_id, None)));
             Some(TabBarToken::new(self))
         } else {
             unsafe { sys::igEndTabBar() };
@@ -221,18 +241,95 @@ impl Ui {
         opened: Option<&mut bool>,
         flags: TabItemFlags,
     ) -> Option<TabItemToken<'_>> {
+        let label = self.scratch_txt(label);
+        let tab_id = Id(unsafe { sys::igGetID_Str(label) });
         let should_render = unsafe {
             sys::igBeginTabItem(
-                self.scratch_txt(label),
+                label,
                 opened.map(|x| x as *mut bool).unwrap_or(ptr::null_mut()),
                 flags.bits() as i32,
             )
         };
 
         if should_render {
+            TAB_BARS.with(|bars| {
+                if let Some((_, selected)) = bars.borrow_mut().last_mut() {
+                    *selected = Some(tab_id);
+                }
+            });
             Some(TabItemToken::new(self))
         } else {
             None
         }
     }
+
+    /// Creates a tab which behaves like a button, such as a "+" tab which opens a new document.
+    ///
+    /// Returns true if the tab was clicked. This passes no flags. To pass flags explicitly, use
+    /// [tab_item_button_with_flags](Self::tab_item_button_with_flags).
+    #[doc(alias = "TabItemButton")]
+    pub fn tab_item_button(&self, label: impl AsRef<str>) -> bool {
+        self.tab_item_button_with_flags(label, TabItemFlags::empty())
+    }
+
+    /// Creates a tab which behaves like a button, and returns true if it was clicked.
+    ///
+    /// [`TabItemFlags::LEADING`] and [`TabItemFlags::TRAILING`] place the button at either end of
+    /// the tab bar.
+    #[doc(alias = "TabItemButton")]
+    pub fn tab_item_button_with_flags(&self, label: impl AsRef<str>, flags: TabItemFlags) -> bool {
+        unsafe { sys::igTabItemButton(self.scratch_txt(label), flags.bits() as i32) }
+    }
+
+    /// Notifies the current tab bar that the tab with the given label is closed before it is
+    /// submitted, which avoids a one-frame flicker when a tab is closed from outside the tab bar
+    /// (for example from a menu).
+    ///
+    /// Call this after beginning the tab bar and before submitting its tabs.
+    #[doc(alias = "SetTabItemClosed")]
+    pub fn set_tab_item_closed(&self, label: impl AsRef<str>) {
+        unsafe { sys::igSetTabItemClosed(self.scratch_txt(label)) }
+    }
+
+    /// Returns the id of the tab last selected in the tab bar with the given id, or `None` if the
+    /// tab bar hasn't been submitted recently.
+    ///
+    /// Only tab bars created with [`tab_bar`](Self::tab_bar) or
+    /// [`tab_bar_with_flags`](Self::tab_bar_with_flags) are tracked. This returns `None` for other
+    /// tab bars, such as the tab bars of dock nodes.
+    ///
+    /// Must be called from the same id scope as the tab bar. The returned id equals
+    /// [`Ui::new_id_str`] of the tab's label, computed inside the tab bar.
+    pub fn selected_tab_id(&self, tab_bar_id: impl AsRef<str>) -> Option<Id> {
+        let bar_id = self.new_id_str(tab_bar_id);
+        self.with_item_state(bar_id, |selected: &mut SelectedTab| selected.0)
+    }
+}
+
+#[test]
+fn test_selected_tab_id() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let mut ids = None;
+    for frame in 0..4 {
+        let ui = ctx.new_frame();
+        ui.window("tabs").build(|| {
+            if let Some(_bar) = ui.tab_bar("bar") {
+                ids = Some((ui.new_id_str("first"), ui.new_id_str("second")));
+                ui.set_tab_item_closed("closed");
+                let flags = if frame == 2 {
+                    TabItemFlags::SET_SELECTED
+                } else {
+                    TabItemFlags::empty()
+                };
+                ui.tab_item("first");
+                ui.tab_item_with_flags("second", None, flags);
+                assert!(!ui.tab_item_button_with_flags("+", TabItemFlags::TRAILING));
+            }
+            let (first, second) = ids.unwrap();
+            // `SET_SELECTED` takes effect on the next frame.
+            let expected = if frame < 3 { first } else { second };
+            assert_eq!(ui.selected_tab_id("bar"), Some(expected));
+        });
+        ctx.render();
+    }
 }
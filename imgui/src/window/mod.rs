@@ -9,6 +9,7 @@ use crate::{Condition, Ui};
 pub(crate) mod child_window;
 pub(crate) mod content_region;
 pub(crate) mod scroll;
+pub(crate) mod splitter;
 
 bitflags! {
     /// Window hover check option flags
@@ -157,6 +158,117 @@ impl Ui {
         unsafe { sys::igGetWindowSize(&mut out) };
         out.into()
     }
+    /// Returns the viewport the current window is in
+    #[cfg(feature = "docking")]
+    #[doc(alias = "GetWindowViewport")]
+    pub fn window_viewport(&self) -> &crate::Viewport {
+        unsafe {
+            // safe because Viewport is a transparent wrapper around sys::ImGuiViewport
+            // and the viewport lives at least as long as the current frame.
+            &*(sys::igGetWindowViewport() as *const crate::Viewport)
+        }
+    }
+    /// Returns the DPI scale of the viewport the current window is in.
+    ///
+    /// Custom drawing can be multiplied by this to match the scale of the OS window.
+    #[cfg(feature = "docking")]
+    #[doc(alias = "GetWindowDpiScale")]
+    pub fn window_dpi_scale(&self) -> f32 {
+        unsafe { sys::igGetWindowDpiScale() }
+    }
+    /// Places the next window in the viewport identified by `viewport_id`.
+    ///
+    /// See also [`Window::viewport`].
+    #[cfg(feature = "docking")]
+    #[doc(alias = "SetNextWindowViewport")]
+    pub fn set_next_window_viewport(&self, viewport_id: crate::Id) {
+        unsafe { sys::igSetNextWindowViewport(viewport_id.0) }
+    }
+    /// Tries to find the viewport whose `platform_handle` is `platform_handle`, e.g. the
+    /// viewport of a given OS window.
+    #[cfg(feature = "docking")]
+    #[doc(alias = "FindViewportByPlatformHandle")]
+    // The handle is only compared against the viewports' handles, never dereferenced
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    pub fn viewport_by_platform_handle(
+        &self,
+        platform_handle: *mut std::ffi::c_void,
+    ) -> Option<&crate::Viewport> {
+        unsafe {
+            // safe because Viewport is a transparent wrapper around sys::ImGuiViewport
+            (sys::igFindViewportByPlatformHandle(platform_handle) as *const crate::Viewport)
+                .as_ref()
+        }
+    }
+}
+
+/// Data passed to a window size callback, see [`Window::size_constraints_callback`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SizeCallbackData {
+    /// Position of the window
+    pub pos: [f32; 2],
+    /// Current size of the window
+    pub current_size: [f32; 2],
+    /// Size the window is about to take, already clamped to the minimum and maximum size
+    /// constraints
+    pub desired_size: [f32; 2],
+}
+
+impl SizeCallbackData {
+    /// Returns the desired size with its height adjusted to keep `width / height == ratio`.
+    ///
+    /// Panics if `ratio` is not finite and positive.
+    pub fn keep_aspect_ratio(&self, ratio: f32) -> [f32; 2] {
+        assert_aspect_ratio(ratio);
+        let [width, _] = self.desired_size;
+        [width, (width / ratio).floor()]
+    }
+
+    /// Returns the desired size rounded to the nearest multiple of `step` on each axis.
+    ///
+    /// Axes with a step of 0.0 or less are left unchanged.
+    pub fn snap_to_step(&self, step: [f32; 2]) -> [f32; 2] {
+        let snap = |v: f32, step: f32| {
+            if step > 0.0 {
+                ((v / step).round() * step).max(step)
+            } else {
+                v
+            }
+        };
+        [
+            snap(self.desired_size[0], step[0]),
+            snap(self.desired_size[1], step[1]),
+        ]
+    }
+}
+
+fn assert_aspect_ratio(ratio: f32) {
+    assert!(
+        ratio.is_finite() && ratio > 0.0,
+        "Aspect ratio must be finite and positive, got {}",
+        ratio
+    );
+}
+
+type SizeCallbackFn<'a> = Box<dyn FnMut(SizeCallbackData) -> [f32; 2] + 'a>;
+
+struct SizeCallback<'a>(SizeCallbackFn<'a>);
+
+impl std::fmt::Debug for SizeCallback<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad("SizeCallback")
+    }
+}
+
+unsafe extern "C" fn size_callback(data: *mut sys::ImGuiSizeCallbackData) {
+    let data = &mut *data;
+    let callback = &mut *(data.UserData as *mut SizeCallbackFn<'_>);
+    let size = callback(SizeCallbackData {
+        pos: data.Pos.into(),
+        current_size: data.CurrentSize.into(),
+        desired_size: data.DesiredSize.into(),
+    });
+    data.DesiredSize = size.into();
 }
 
 /// Builder for a window
@@ -173,6 +285,9 @@ pub struct Window<'ui, 'a, Label> {
     size: MintVec2,
     size_cond: Condition,
     size_constraints: Option<(MintVec2, MintVec2)>,
+    size_callback: Option<SizeCallback<'a>>,
+    #[cfg(feature = "docking")]
+    viewport: Option<crate::Id>,
     content_size: MintVec2,
     collapsed: bool,
     collapsed_cond: Condition,
@@ -195,6 +310,9 @@ impl<'ui, 'a, Label: AsRef<str>> Window<'ui, 'a, Label> {
             size: [0.0, 0.0].into(),
             size_cond: Condition::Never,
             size_constraints: None,
+            size_callback: None,
+            #[cfg(feature = "docking")]
+            viewport: None,
             content_size: [0.0, 0.0].into(),
             collapsed: false,
             collapsed_cond: Condition::Never,
@@ -250,6 +368,54 @@ impl<'ui, 'a, Label: AsRef<str>> Window<'ui, 'a, Label> {
         self.size_constraints = Some((size_min.into(), size_max.into()));
         self
     }
+    /// Sets a callback which can correct the window size whenever it changes.
+    ///
+    /// The callback receives the current and desired size, and returns the size the window
+    /// should take. The desired size is clamped to [`size_constraints`](Self::size_constraints)
+    /// first, if they were set.
+    ///
+    /// ```no_run
+    /// # let mut ctx = imgui::Context::create();
+    /// # let ui = ctx.frame();
+    /// // Only allow heights which are multiples of the width
+    /// ui.window("Tiles")
+    ///     .size_constraints_callback(|data| {
+    ///         let [w, h] = data.desired_size;
+    ///         [w, (h / w).round().max(1.0) * w]
+    ///     })
+    ///     .build(|| {});
+    /// ```
+    #[inline]
+    pub fn size_constraints_callback(
+        mut self,
+        callback: impl FnMut(SizeCallbackData) -> [f32; 2] + 'a,
+    ) -> Self {
+        self.size_callback = Some(SizeCallback(Box::new(callback)));
+        self
+    }
+    /// Keeps the window at a fixed aspect ratio (`width / height`), adjusting the height when the
+    /// window is resized.
+    ///
+    /// Panics if `ratio` is not finite and positive.
+    #[inline]
+    pub fn keep_aspect_ratio(self, ratio: f32) -> Self {
+        assert_aspect_ratio(ratio);
+        self.size_constraints_callback(move |data| data.keep_aspect_ratio(ratio))
+    }
+    /// Snaps the window size to multiples of `step`, e.g. the cell size of a grid.
+    #[inline]
+    pub fn snap_size(self, step: impl Into<MintVec2>) -> Self {
+        let step: [f32; 2] = step.into().into();
+        self.size_constraints_callback(move |data| data.snap_to_step(step))
+    }
+    /// Places the window in the viewport identified by `viewport_id`, e.g. to pin a tool window
+    /// to a specific OS window.
+    #[cfg(feature = "docking")]
+    #[inline]
+    pub fn viewport(mut self, viewport_id: crate::Id) -> Self {
+        self.viewport = Some(viewport_id);
+        self
+    }
     /// Sets the window content size, which can be used to enforce scrollbars.
     ///
     /// Does not include window decorations (title bar, menu bar, etc.). Set one of the values to
@@ -487,7 +653,7 @@ impl<'ui, 'a, Label: AsRef<str>> Window<'ui, 'a, Label> {
     ///
     /// Returns `None` if the window is not visible and no content should be rendered.
     #[must_use]
-    pub fn begin(self) -> Option<WindowToken<'ui>> {
+    pub fn begin(mut self) -> Option<WindowToken<'ui>> {
         if self.pos_cond != Condition::Never {
             unsafe {
                 sys::igSetNextWindowPos(
@@ -500,17 +666,32 @@ impl<'ui, 'a, Label: AsRef<str>> Window<'ui, 'a, Label> {
         if self.size_cond != Condition::Never {
             unsafe { sys::igSetNextWindowSize(self.size.into(), self.size_cond as i32) };
         }
-        if let Some((size_min, size_max)) = self.size_constraints {
-            // TODO: callback support
+        // The callback is only invoked from within `igBegin`, so it can live on our stack
+        let mut callback_fn = self.size_callback.take().map(|callback| callback.0);
+        if self.size_constraints.is_some() || callback_fn.is_some() {
+            let (size_min, size_max) = self
+                .size_constraints
+                .unwrap_or(([0.0, 0.0].into(), [f32::MAX, f32::MAX].into()));
+            let (callback, user_data) = match &mut callback_fn {
+                Some(callback) => (
+                    Some(size_callback as unsafe extern "C" fn(_)),
+                    callback as *mut SizeCallbackFn<'_> as *mut std::ffi::c_void,
+                ),
+                None => (None, ptr::null_mut()),
+            };
             unsafe {
                 sys::igSetNextWindowSizeConstraints(
                     size_min.into(),
                     size_max.into(),
-                    None,
-                    ptr::null_mut(),
+                    callback,
+                    user_data,
                 )
             };
         }
+        #[cfg(feature = "docking")]
+        if let Some(viewport_id) = self.viewport {
+            unsafe { sys::igSetNextWindowViewport(viewport_id.0) };
+        }
         if self.content_size.x != 0.0 || self.content_size.y != 0.0 {
             unsafe { sys::igSetNextWindowContentSize(self.content_size.into()) };
         }
@@ -557,3 +738,56 @@ create_token!(
     /// Ends a window
     drop { sys::igEnd() }
 );
+
+#[test]
+fn test_window_size_constraints_callback() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let mut calls = 0;
+    let mut sizes = Vec::new();
+    for _ in 0..2 {
+        let ui = ctx.new_frame();
+        ui.window("aspect")
+            .size([160.0, 50.0], Condition::Always)
+            .keep_aspect_ratio(16.0 / 9.0)
+            .build(|| sizes.push(ui.window_size()));
+        ui.window("snapped")
+            .size([95.0, 95.0], Condition::Always)
+            .size_constraints_callback(|data| {
+                calls += 1;
+                data.snap_to_step([20.0, 30.0])
+            })
+            .build(|| sizes.push(ui.window_size()));
+        ctx.render();
+    }
+    assert!(calls > 0);
+    assert_eq!(sizes[sizes.len() - 2..], [[160.0, 90.0], [100.0, 90.0]]);
+}
+
+#[test]
+#[cfg(feature = "docking")]
+fn test_window_viewport_queries() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let main_id = ctx.main_viewport().id;
+    let mut handle = 0u8;
+    let handle = &mut handle as *mut u8 as *mut std::ffi::c_void;
+    ctx.main_viewport_mut().platform_handle = handle;
+
+    let ui = ctx.new_frame();
+    let found = ui.window("pinned").viewport(main_id).build(|| {
+        assert_eq!(ui.window_viewport().id, main_id);
+        assert_eq!(ui.window_dpi_scale(), ui.window_viewport().dpi_scale);
+        ui.viewport_by_platform_handle(handle).map(|v| v.id)
+    });
+    assert_eq!(found, Some(Some(main_id)));
+    assert!(ui
+        .viewport_by_platform_handle(std::ptr::null_mut())
+        .is_none());
+}
+
+#[test]
+#[should_panic(expected = "Aspect ratio must be finite and positive")]
+fn test_window_keep_aspect_ratio_rejects_zero() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.new_frame();
+    let _ = ui.window("aspect").keep_aspect_ratio(0.0);
+}
@@ -0,0 +1,258 @@
+use crate::input::mouse::{MouseButton, MouseCursor};
+use crate::math::MintVec2;
+use crate::style::StyleColor;
+use crate::window::child_window::ChildFlags;
+use crate::Ui;
+
+/// How a [`Splitter`] lays out its two panes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SplitDirection {
+    /// The panes are side by side, separated by a vertical divider.
+    Horizontal,
+    /// The panes are stacked, separated by a horizontal divider.
+    Vertical,
+}
+
+impl Ui {
+    /// Creates an unbuilt splitter, see [`Splitter`].
+    pub fn splitter<Label: AsRef<str>>(
+        &self,
+        label: Label,
+        direction: SplitDirection,
+    ) -> Splitter<'_, Label> {
+        Splitter {
+            label,
+            direction,
+            ratio: 0.5,
+            min_sizes: [0.0, 0.0],
+            size: [0.0, 0.0],
+            thickness: None,
+            child_flags: ChildFlags::empty(),
+            ui: self,
+        }
+    }
+}
+
+/// Builder for two child windows separated by a divider which can be dragged to resize them.
+///
+/// The position of the divider is stored as a ratio in the state storage of the current window,
+/// under the id of the label, so it is kept across frames.
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// ui.splitter("editor", SplitDirection::Horizontal)
+///     .ratio(0.25)
+///     .min_sizes(100.0, 200.0)
+///     .build(
+///         || ui.text("Files"),
+///         || ui.text("Contents"),
+///     );
+/// ```
+#[derive(Copy, Clone, Debug)]
+#[must_use]
+pub struct Splitter<'ui, Label> {
+    label: Label,
+    direction: SplitDirection,
+    ratio: f32,
+    min_sizes: [f32; 2],
+    size: [f32; 2],
+    thickness: Option<f32>,
+    child_flags: ChildFlags,
+    ui: &'ui Ui,
+}
+
+impl<'ui, Label: AsRef<str>> Splitter<'ui, Label> {
+    /// Sets the initial part of the space given to the first pane, from 0.0 to 1.0. Once the
+    /// divider has been dragged, the stored ratio is used instead.
+    ///
+    /// Defaults to 0.5.
+    #[inline]
+    pub fn ratio(mut self, ratio: f32) -> Self {
+        self.ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+    /// Sets the minimum sizes of the first and second panes along the split direction.
+    ///
+    /// Defaults to 0.
+    #[inline]
+    pub fn min_sizes(mut self, first: f32, second: f32) -> Self {
+        self.min_sizes = [first, second];
+        self
+    }
+    /// Sets the size of the whole splitter.
+    ///
+    /// Zero values use the available content region on that axis, and negative values are
+    /// subtracted from it. Defaults to `[0.0, 0.0]`.
+    #[inline]
+    pub fn size(mut self, size: impl Into<MintVec2>) -> Self {
+        self.size = size.into().into();
+        self
+    }
+    /// Sets the thickness of the divider.
+    ///
+    /// Defaults to the item spacing of the style along the split direction.
+    #[inline]
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = Some(thickness);
+        self
+    }
+    /// Sets the flags of both child windows, such as [`ChildFlags::BORDERS`].
+    #[inline]
+    pub fn child_flags(mut self, child_flags: ChildFlags) -> Self {
+        self.child_flags = child_flags;
+        self
+    }
+    /// Builds the splitter, running `first` and `second` to construct the contents of the panes.
+    ///
+    /// Returns true if the divider was moved.
+    pub fn build(self, first: impl FnOnce(), second: impl FnOnce()) -> bool {
+        let ui = self.ui;
+        let (axis, cursor) = match self.direction {
+            SplitDirection::Horizontal => (0, MouseCursor::ResizeEW),
+            SplitDirection::Vertical => (1, MouseCursor::ResizeNS),
+        };
+        let cross = 1 - axis;
+        let thickness = self
+            .thickness
+            .unwrap_or_else(|| ui.clone_style().item_spacing[axis]);
+        let avail = ui.content_region_avail();
+        let mut size = self.size;
+        for (size, avail) in size.iter_mut().zip(avail) {
+            if *size <= 0.0 {
+                *size = (avail + *size).max(1.0);
+            }
+        }
+        let total = (size[axis] - thickness).max(0.0);
+        let [min_first, min_second] = self.min_sizes;
+        let clamp = |length: f32| {
+            length
+                .min(total - min_second)
+                .max(min_first)
+                .clamp(0.0, total)
+        };
+
+        let _id = ui.push_id(self.label.as_ref());
+        let _group = ui.begin_group();
+        let ratio_id = ui.new_id_str("##ratio");
+        let drag_start_id = ui.new_id_str("##drag_start");
+        let mut storage = ui.state_storage();
+        let mut first_length = clamp(total * storage.get_float(ratio_id, self.ratio));
+
+        let pos = ui.cursor_screen_pos();
+        let mut divider_pos = pos;
+        divider_pos[axis] += first_length;
+        let mut divider_size = size;
+        divider_size[axis] = thickness;
+        ui.set_cursor_screen_pos(divider_pos);
+        ui.invisible_button("##divider", divider_size);
+        let (hovered, active) = (ui.is_item_hovered(), ui.is_item_active());
+        if ui.is_item_activated() {
+            storage.set_float(drag_start_id, first_length);
+        }
+        let mut moved = false;
+        if active {
+            let delta = ui.mouse_drag_delta_with_threshold(MouseButton::Left, 0.0)[axis];
+            let start = storage.get_float(drag_start_id, first_length);
+            let length = clamp(start + delta);
+            if length != first_length {
+                first_length = length;
+                moved = true;
+                if total > 0.0 {
+                    storage.set_float(ratio_id, first_length / total);
+                }
+            }
+        }
+        if hovered || active {
+            ui.set_mouse_cursor(Some(cursor));
+        }
+
+        // The divider is drawn where it is after dragging, rather than where its item was added.
+        divider_pos[axis] = pos[axis] + first_length;
+        let divider_end = [
+            divider_pos[0] + divider_size[0],
+            divider_pos[1] + divider_size[1],
+        ];
+        let draw_list = ui.get_window_draw_list();
+        if hovered || active {
+            let color = if active {
+                StyleColor::SeparatorActive
+            } else {
+                StyleColor::SeparatorHovered
+            };
+            draw_list
+                .add_rect(divider_pos, divider_end, ui.style_color_u32(color, 1.0))
+                .filled(true)
+                .build();
+        } else {
+            let mut from = divider_pos;
+            from[axis] += thickness / 2.0;
+            let mut to = from;
+            to[cross] += size[cross];
+            draw_list
+                .add_line(from, to, ui.style_color_u32(StyleColor::Separator, 1.0))
+                .build();
+        }
+        drop(draw_list);
+
+        let mut first_size = size;
+        // A zero size would make the child fill the available space.
+        first_size[axis] = first_length.max(1.0);
+        ui.set_cursor_screen_pos(pos);
+        ui.child_window("##first")
+            .size(first_size)
+            .child_flags(self.child_flags)
+            .build(first);
+
+        let mut second_pos = pos;
+        second_pos[axis] += first_length + thickness;
+        let mut second_size = size;
+        second_size[axis] = (total - first_length).max(1.0);
+        ui.set_cursor_screen_pos(second_pos);
+        ui.child_window("##second")
+            .size(second_size)
+            .child_flags(self.child_flags)
+            .build(second);
+        moved
+    }
+}
+
+#[test]
+fn test_splitter_drag() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.io_mut().config_input_trickle_event_queue = false;
+    let (mut first_width, mut second_width) = (0.0, 0.0);
+    for frame in 0..6 {
+        // Window padding and item spacing are 8, so the 384 wide splitter starts at 8 and its
+        // divider at 8 + (384 - 8) / 2.
+        match frame {
+            0 => ctx.io_mut().add_mouse_pos_event([200.0, 50.0]),
+            2 => ctx.io_mut().add_mouse_button_event(MouseButton::Left, true),
+            3 => ctx.io_mut().add_mouse_pos_event([240.0, 50.0]),
+            4 => ctx.io_mut().add_mouse_pos_event([390.0, 50.0]),
+            _ => {}
+        }
+        let ui = ctx.new_frame();
+        ui.window("split")
+            .position([0.0, 0.0], crate::Condition::Always)
+            .size([400.0, 300.0], crate::Condition::Always)
+            .no_decoration()
+            .build(|| {
+                let moved = ui
+                    .splitter("panes", SplitDirection::Horizontal)
+                    .min_sizes(50.0, 100.0)
+                    .build(
+                        || first_width = ui.window_size()[0],
+                        || second_width = ui.window_size()[0],
+                    );
+                assert_eq!(moved, frame == 3 || frame == 4);
+                if frame == 3 {
+                    assert_eq!((first_width, second_width), (228.0, 148.0));
+                }
+            });
+        ctx.render();
+    }
+    // Dragged past the minimum size of the second pane.
+    assert_eq!((first_width, second_width), (276.0, 100.0));
+}